@@ -1,3 +1,4 @@
+import json
 import uuid
 import base64
 import aiohttp
@@ -5,8 +6,9 @@ from typing import Any, Dict, Optional, Union, List, Tuple
 from urllib.parse import urljoin
 import asyncio
 from namada_types.general import ValidatorState, ValidatorMetaData, U64
-from namada_types.rust_py import address_parse, commission_pair_parse, proposal_parse, votes_parse, \
-    proposal_result_parse
+from namada_types.rust_py import address_parse, commission_pair_parse
+from namada_types.parsers import validator_addresses_parse, zip_addresses_states, proposal_result_parse, \
+    supply_parse, proposal_parse, delegations_parse, votes_parse, weighted_validators_parse, slashes_parse
 
 
 class Result:
@@ -137,6 +139,13 @@ class NamadaProvider:
             return Result(True, U64.parse(result.data))
         return result
 
+    async def get_epoch_at_height(self, height: int) -> Result:
+        params = {"path": f"/shell/epoch_at_height/{height}"}
+        result = await self._fetch_abci_query_value(params)
+        if result.success:
+            return Result(True, U64.parse(result.data[1:]) if result.data[:1] == b"\x01" else None)
+        return result
+
     async def get_operator_address_from_tm(self, tm_address: str) -> Result:
         params = {"path": f"/vp/pos/validator_by_tm_addr/{tm_address}"}
         result = await self._fetch_abci_query_value(params)
@@ -167,13 +176,80 @@ class NamadaProvider:
             return Result(True, commission_pair_parse(result.data[1:]))
         return result
 
-    async def get_validator_state(self, validator_address: str) -> Result:
-        params = {"path": f"/vp/pos/validator/state/{validator_address}"}
+    async def get_validator_state(self, validator_address: str, epoch: Optional[int] = None) -> Result:
+        path = f"/vp/pos/validator/state/{validator_address}"
+        params = {"path": f"{path}/{epoch}" if epoch is not None else path}
         result = await self._fetch_abci_query_value(params)
         if result.success:
             return Result(True, ValidatorState.parse(result.data[1:]).__class__.__name__)
         return result
 
+    async def get_validator_slashes(self, validator_address: str, group_by_epoch: bool = False) -> Result:
+        params = {"path": f"/vp/pos/validator_slashes/{validator_address}"}
+        result = await self._fetch_abci_query_value(params)
+        if result.success:
+            return Result(True, slashes_parse(result.data, group_by_epoch))
+        return result
+
+    async def get_validator_addresses(self) -> Result:
+        params = {"path": f"/vp/pos/validator/addresses"}
+        result = await self._fetch_abci_query_value(params)
+        if result.success:
+            return Result(True, validator_addresses_parse(result.data))
+        return result
+
+    async def get_validators_with_states(self) -> Result:
+        params = {"path": f"/vp/pos/validator/addresses"}
+        result = await self._fetch_abci_query_value(params)
+        if not result.success:
+            return result
+        addresses = json.loads(validator_addresses_parse(result.data))
+        states = await asyncio.gather(*(self.get_validator_state(address) for address in addresses))
+        for state in states:
+            if not state.success:
+                return state
+        states = {address: state.data for address, state in zip(addresses, states)}
+        return Result(True, zip_addresses_states(addresses, json.dumps(states)))
+
+    async def get_consensus_validator_set(self) -> Result:
+        params = {"path": f"/vp/pos/validator_set/consensus"}
+        result = await self._fetch_abci_query_value(params)
+        if result.success:
+            return Result(True, weighted_validators_parse(result.data))
+        return result
+
+    async def get_below_capacity_validator_set(self) -> Result:
+        params = {"path": f"/vp/pos/validator_set/below_capacity"}
+        result = await self._fetch_abci_query_value(params)
+        if result.success:
+            return Result(True, weighted_validators_parse(result.data))
+        return result
+
+    async def get_delegations(self, owner_address: str) -> Result:
+        params = {"path": f"/vp/pos/delegations_at/{owner_address}"}
+        result = await self._fetch_abci_query_value(params)
+        if result.success:
+            return Result(True, delegations_parse(result.data))
+        return result
+
+    async def get_token_denomination(self, token_address: str) -> Result:
+        params = {"path": f"/vp/token/denomination/{token_address}"}
+        result = await self._fetch_abci_query_value(params)
+        if result.success:
+            value = result.data[1:]
+            return Result(True, value[0] if value else None)
+        return result
+
+    async def get_total_supply(self, token_address: str) -> Result:
+        denom = await self.get_token_denomination(token_address)
+        if not denom.success:
+            return denom
+        params = {"path": f"/vp/token/total_supply/{token_address}"}
+        result = await self._fetch_abci_query_value(params)
+        if result.success:
+            return Result(True, supply_parse(result.data, denom.data or 0))
+        return result
+
     async def get_governance_parameters(self) -> Result:
         params = {"path": f"/vp/governance/parameters"}
         result = await self._fetch_abci_query_value(params)
@@ -181,38 +257,46 @@ class NamadaProvider:
             return Result(True, result.data)
         return result
 
-    async def get_proposal_detail(self, proposal_id: int, epoch: int) -> Result:
+    async def get_proposal_detail(self, proposal_id: int, epoch: int, **options) -> Result:
         params = {"path": f"/vp/governance/proposal/{proposal_id}"}
         result = await self._fetch_abci_query_value(params)
         if result.success:
             value = result.data[1:]
             if value:
-                return Result(True, proposal_parse(value, epoch))
+                return Result(True, proposal_parse(value, epoch, **options))
             else:
                 return Result(True, None)
         return result
 
-    async def get_votes_info(self, proposal_id: int) -> Result:
+    async def get_votes_info(self, proposal_id: int, **options) -> Result:
         params = {"path": f"/vp/governance/proposal/{proposal_id}/votes"}
         result = await self._fetch_abci_query_value(params)
         if result.success:
             value = result.data
             if value:
-                return Result(True, votes_parse(value))
+                return Result(True, votes_parse(value, **options))
             else:
                 return Result(True, None)
         return result
 
     async def get_proposal_result(self, proposal_id: int) -> Result:
+        """Fetch a proposal's stored result as `proposal_result_parse` JSON.
+
+        A proposal that exists but has not been tallied yet gives the `provisional` JSON; `data` is None only when
+        no proposal has this id, as the node returns no result for both.
+        """
         params = {"path": f"/vp/governance/stored_proposal_result/{proposal_id}"}
         result = await self._fetch_abci_query_value(params)
-        if result.success:
-            value = result.data[1:]
-            if value:
-                return Result(True, proposal_result_parse(value))
-            else:
+        if not result.success:
+            return result
+        value = result.data[1:]
+        if not value:
+            proposal = await self._fetch_abci_query_value({"path": f"/vp/governance/proposal/{proposal_id}"})
+            if not proposal.success:
+                return proposal
+            if not proposal.data[1:]:
                 return Result(True, None)
-        return result
+        return Result(True, proposal_result_parse(value))
 
 
 
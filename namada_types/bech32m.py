@@ -0,0 +1,79 @@
+from typing import List, Tuple
+
+CHARSET = "qpzry9x8gf2tvdw0s3jn54khce6mua7l"
+BECH32M_CONST = 0x2bc830a3
+BECH32_CONST = 1
+
+
+def _polymod(values: List[int]) -> int:
+    generator = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3]
+    chk = 1
+    for value in values:
+        top = chk >> 25
+        chk = (chk & 0x1ffffff) << 5 ^ value
+        for i in range(5):
+            chk ^= generator[i] if ((top >> i) & 1) else 0
+    return chk
+
+
+def _hrp_expand(hrp: str) -> List[int]:
+    return [ord(x) >> 5 for x in hrp] + [0] + [ord(x) & 31 for x in hrp]
+
+
+def _convert_bits(data: bytes, from_bits: int, to_bits: int, pad: bool = True) -> List[int]:
+    acc = 0
+    bits = 0
+    ret = []
+    max_value = (1 << to_bits) - 1
+    for value in data:
+        acc = (acc << from_bits) | value
+        bits += from_bits
+        while bits >= to_bits:
+            bits -= to_bits
+            ret.append((acc >> bits) & max_value)
+    if pad and bits:
+        ret.append((acc << (to_bits - bits)) & max_value)
+    elif not pad and (bits >= from_bits or (acc << (to_bits - bits)) & max_value):
+        raise ValueError("Invalid bech32m padding")
+    return ret
+
+
+def _check_hrp(hrp: str) -> None:
+    if not hrp or any(ord(x) < 33 or ord(x) > 126 for x in hrp):
+        raise ValueError(f"Invalid bech32m human-readable part: {hrp!r}")
+
+
+def encode(hrp: str, data: bytes) -> str:
+    """Encode raw bytes as a bech32m string, the variant Namada uses for addresses."""
+    _check_hrp(hrp)
+    if hrp.lower() != hrp:
+        raise ValueError(f"Invalid bech32m human-readable part: {hrp!r} must be lowercase")
+    values = _convert_bits(data, 8, 5)
+    polymod = _polymod(_hrp_expand(hrp) + values + [0] * 6) ^ BECH32M_CONST
+    checksum = [(polymod >> 5 * (5 - i)) & 31 for i in range(6)]
+    return hrp + "1" + "".join(CHARSET[d] for d in values + checksum)
+
+
+def decode(value: str, allow_bech32: bool = False) -> Tuple[str, bytes]:
+    """Decode a bech32m string into its human-readable part and raw bytes, verifying the checksum.
+
+    With `allow_bech32` a checksum of the original bech32 variant, still used by most Cosmos chains, is accepted too.
+    """
+    if value.lower() != value and value.upper() != value:
+        raise ValueError("Invalid bech32m string: mixed case")
+    value = value.lower()
+    pos = value.rfind("1")
+    if pos < 1 or pos + 7 > len(value):
+        raise ValueError("Invalid bech32m string: missing separator or checksum")
+    hrp = value[:pos]
+    _check_hrp(hrp)
+    try:
+        values = [CHARSET.index(x) for x in value[pos + 1:]]
+    except ValueError:
+        raise ValueError("Invalid bech32m string: invalid data character")
+    polymod = _polymod(_hrp_expand(hrp) + values)
+    if polymod == BECH32_CONST and not allow_bech32:
+        raise ValueError("Invalid bech32m checksum: string uses the bech32 variant")
+    if polymod not in (BECH32M_CONST, BECH32_CONST):
+        raise ValueError("Invalid bech32m checksum")
+    return hrp, bytes(_convert_bits(bytes(values[:-6]), 5, 8, pad=False))
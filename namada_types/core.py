@@ -0,0 +1,198 @@
+import struct
+from typing import Any, Callable, List, NamedTuple, Optional
+from namada_types import bech32m
+
+ADDRESS_HRP = "tnam"
+ADDRESS_HASH_LEN = 20
+DEC_PRECISION = 12
+U64_MAX = 2 ** 64 - 1
+
+VALIDATOR_STATES = ("Consensus", "BelowCapacity", "BelowThreshold", "Inactive", "Jailed")
+TALLY_RESULTS = ("Passed", "Rejected")
+TALLY_TYPES = ("TwoThirds", "OneHalfOverOneThird", "LessOneHalfOverOneThirdNay")
+PROPOSAL_TYPES = ("Default", "PGF steward", "PGF funding")
+PROPOSAL_STATUSES = ("pending", "on-going", "ended")
+VOTE_CHOICES = ("Yay", "Nay", "Abstain")
+ADD_REMOVE = ("Add", "Remove")
+SLASH_TYPES = ("DuplicateVote", "LightClientAttack")
+HASH_LEN = 32
+MASP_PAYMENT_ADDRESS_HRP = "znam"
+MASP_PINNED_PAYMENT_ADDRESS_HRP = "znampin"
+MASP_VIEWING_KEY_HRP = "zvknam"
+MASP_PAYMENT_ADDRESS_LEN = 43
+MASP_VIEWING_KEY_LEN = 169
+IBC_IDENTIFIER_CHARS = frozenset("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789._+-#[]<>")
+
+# Internal addresses in Borsh variant order: (name, string discriminant, carries a hash)
+INTERNAL_ADDRESSES = (
+    ("PoS", 2, False),
+    ("PosSlashPool", 3, False),
+    ("Parameters", 4, False),
+    ("Ibc", 6, False),
+    ("IbcToken", 13, True),
+    ("Governance", 5, False),
+    ("EthBridge", 7, False),
+    ("EthBridgePool", 8, False),
+    ("Erc20", 11, True),
+    ("Nut", 12, True),
+    ("Multitoken", 9, False),
+    ("Pgf", 10, False),
+    ("Masp", 14, False),
+)
+
+
+class UnexpectedEnd(ValueError):
+    """Raised when the input ends before the value being decoded is complete."""
+
+
+class Reader:
+    """Cursor over a Borsh-encoded byte string."""
+
+    def __init__(self, data: bytes):
+        self.data = bytes(data)
+        self.pos = 0
+
+    def read(self, n: int) -> bytes:
+        if self.pos + n > len(self.data):
+            raise UnexpectedEnd("Decoding failed: Unexpected length of input")
+        chunk = self.data[self.pos:self.pos + n]
+        self.pos += n
+        return chunk
+
+    def u8(self) -> int:
+        return self.read(1)[0]
+
+    def u32(self) -> int:
+        return struct.unpack("<I", self.read(4))[0]
+
+    def u64(self) -> int:
+        return struct.unpack("<Q", self.read(8))[0]
+
+    def u256(self) -> int:
+        return int.from_bytes(self.read(32), "little")
+
+    def i256(self) -> int:
+        return int.from_bytes(self.read(32), "little", signed=True)
+
+    def string(self, errors: str = "strict") -> str:
+        raw = self.read(self.u32())
+        try:
+            return raw.decode("utf-8", errors)
+        except UnicodeDecodeError as e:
+            raise ValueError(f"Decoding failed: invalid utf-8 sequence of {e.end - e.start} bytes "
+                             f"from index {e.start}")
+
+    def ibc_identifier(self, kind: str, min_len: int, max_len: int) -> str:
+        value = self.string()
+        if not min_len <= len(value) <= max_len:
+            raise ValueError(f"Decoding failed: Error decoding {kind}: identifier `{value}` has invalid length "
+                             f"`{len(value)}` must be between `{min_len}`-`{max_len}` characters")
+        if not set(value) <= IBC_IDENTIFIER_CHARS:
+            raise ValueError(f"Decoding failed: Error decoding {kind}: identifier `{value}` "
+                             "contains invalid characters")
+        return value
+
+    def tag(self, count: int) -> int:
+        tag = self.u8()
+        if tag >= count:
+            raise ValueError(f"Decoding failed: Unexpected variant tag: {tag}")
+        return tag
+
+    def option(self, read_value: Callable[[], Any]) -> Any:
+        return read_value() if self.tag(2) else None
+
+    def vec(self, read_item: Callable[[], Any]) -> List[Any]:
+        return [read_item() for _ in range(self.u32())]
+
+    def finish(self) -> None:
+        if self.pos != len(self.data):
+            raise ValueError("Decoding failed: Not all bytes read")
+
+
+class Writer:
+    """Builds a Borsh-encoded byte string, the inverse of `Reader`."""
+
+    def __init__(self):
+        self.data = bytearray()
+
+    def write(self, data: bytes) -> None:
+        self.data += data
+
+    def u8(self, value: int) -> None:
+        self.data.append(value)
+
+    def u32(self, value: int) -> None:
+        self.data += struct.pack("<I", value)
+
+    def u64(self, value: int) -> None:
+        self.data += struct.pack("<Q", value)
+
+    def string(self, value: str) -> None:
+        raw = value.encode("utf-8")
+        self.u32(len(raw))
+        self.data += raw
+
+
+class Address(NamedTuple):
+    discriminant: int
+    hash: bytes
+
+    @property
+    def kind(self) -> str:
+        return {0: "Implicit", 1: "Established"}.get(self.discriminant, "Internal")
+
+    @property
+    def internal_name(self) -> Optional[str]:
+        return next((name for name, discriminant, _ in INTERNAL_ADDRESSES if discriminant == self.discriminant), None)
+
+    def encode(self) -> str:
+        return bech32m.encode(ADDRESS_HRP, bytes([self.discriminant]) + self.hash)
+
+    @classmethod
+    def decode(cls, value: str) -> "Address":
+        hrp, payload = bech32m.decode(value)
+        if hrp != ADDRESS_HRP:
+            raise ValueError(f"Address belongs to network '{hrp}', expected '{ADDRESS_HRP}'")
+        if len(payload) != ADDRESS_HASH_LEN + 1:
+            raise ValueError(f"Invalid address payload length {len(payload)}")
+        address = cls(payload[0], payload[1:])
+        if address.kind == "Internal" and address.internal_name is None:
+            raise ValueError(f"Unknown address discriminant {address.discriminant}")
+        return address
+
+
+def write_address(writer: Writer, address: Address) -> None:
+    if address.kind != "Internal":
+        writer.u8(0 if address.kind == "Established" else 1)
+        writer.write(address.hash)
+        return
+    index = next(i for i, (_, d, _) in enumerate(INTERNAL_ADDRESSES) if d == address.discriminant)
+    writer.u8(2)
+    writer.u8(index)
+    if INTERNAL_ADDRESSES[index][2]:
+        writer.write(address.hash)
+
+
+def address_order_key(address: Address) -> bytes:
+    """Sort key matching the SDK's derived `Ord` on `Address`, which orders addresses as their Borsh encodings."""
+    writer = Writer()
+    write_address(writer, address)
+    return bytes(writer.data)
+
+
+def format_decimal(value: int, scale: int) -> str:
+    """Render a fixed-point integer with `scale` decimal places, dropping trailing zeros."""
+    sign = "-" if value < 0 else ""
+    integer, fraction = divmod(abs(value), 10 ** scale)
+    fraction_str = str(fraction).rjust(scale, "0").rstrip("0") if scale else ""
+    return f"{sign}{integer}.{fraction_str}" if fraction_str else f"{sign}{integer}"
+
+
+def read_address(reader: Reader) -> Address:
+    tag = reader.tag(3)
+    if tag == 0:
+        return Address(1, reader.read(ADDRESS_HASH_LEN))
+    if tag == 1:
+        return Address(0, reader.read(ADDRESS_HASH_LEN))
+    _, discriminant, has_hash = INTERNAL_ADDRESSES[reader.tag(len(INTERNAL_ADDRESSES))]
+    return Address(discriminant, reader.read(ADDRESS_HASH_LEN) if has_hash else bytes(ADDRESS_HASH_LEN))
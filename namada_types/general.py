@@ -1,4 +1,5 @@
 from borsh_construct import CStruct, String, Option, Enum,U64
+from namada_types.core import VALIDATOR_STATES
 
 ValidatorMetaData = CStruct(
     'email' / String,
@@ -9,11 +10,7 @@ ValidatorMetaData = CStruct(
 )
 
 ValidatorState = Enum(
-    "Consensus",
-    "BelowCapacity",
-    "BelowThreshold",
-    "Inactive",
-    "Jailed",
+    *VALIDATOR_STATES,
     enum_name="ValidatorState",
 )
 
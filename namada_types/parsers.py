@@ -0,0 +1,2191 @@
+import asyncio
+import contextlib
+import contextvars
+import csv
+import functools
+import hashlib
+import io
+import json
+import operator
+import re
+import threading
+import time
+from collections.abc import Mapping
+from concurrent.futures import ProcessPoolExecutor
+from dataclasses import dataclass, replace
+from datetime import datetime, timedelta, timezone
+from decimal import Decimal, InvalidOperation
+from fractions import Fraction
+from typing import Any, Callable, Dict, Iterator, List, Optional, Tuple, Union
+from urllib.parse import urlparse
+from namada_types import bech32m
+from namada_types.core import ADDRESS_HRP, ADD_REMOVE, DEC_PRECISION, HASH_LEN, INTERNAL_ADDRESSES, \
+    MASP_PAYMENT_ADDRESS_HRP, MASP_PAYMENT_ADDRESS_LEN, MASP_PINNED_PAYMENT_ADDRESS_HRP, MASP_VIEWING_KEY_HRP, \
+    MASP_VIEWING_KEY_LEN, PROPOSAL_STATUSES, PROPOSAL_TYPES, SLASH_TYPES, U64_MAX, VOTE_CHOICES, Address, Reader, \
+    TALLY_RESULTS, TALLY_TYPES, UnexpectedEnd, VALIDATOR_STATES, Writer, address_order_key, format_decimal, \
+    read_address, write_address
+
+VALIDATOR_SET_TIERS = ("Consensus", "BelowCapacity")
+METADATA_FIELDS = ("email", "description", "website", "discord_handle", "avatar")
+EMAIL_PATTERN = re.compile(r"^[^@\s<>\"'`]+@[^@\s<>\"'`]+\.[^@\s<>\"'`]+$")
+DISCORD_HANDLE_PATTERN = re.compile(r"^[\w.#-]{2,37}$")
+HOSTNAME_PATTERN = re.compile(r"^[a-z0-9-]+(\.[a-z0-9-]+)+$")
+FILTER_OPERATORS = {
+    "eq": operator.eq,
+    "ne": operator.ne,
+    "gt": operator.gt,
+    "gte": operator.ge,
+    "lt": operator.lt,
+    "lte": operator.le,
+    "in": lambda value, options: value in options,
+}
+DEC_PATTERN = re.compile(r"^(-?)(\d+)(?:\.(\d+))?$")
+TALLY_POWER_FIELDS = ("total_voting_power", "total_yay_power", "total_nay_power", "total_abstain_power")
+
+TALLY_RULES = {
+    "TwoThirds": "under the two-thirds rule, at least two thirds of the total voting power must vote and at least "
+                 "two thirds of the yay and nay power must be yay",
+    "OneHalfOverOneThird": "under the one-half-over-one-third rule, at least one third of the total voting power "
+                           "must vote and more than half of the yay and nay power must be yay",
+    "LessOneHalfOverOneThirdNay": "under the less-than-one-half-nay rule, the proposal passes unless at least one "
+                                  "third of the total voting power votes and more than half of the yay and nay power "
+                                  "is nay",
+}
+
+ENUM_VARIANTS = {
+    "ValidatorState": VALIDATOR_STATES,
+    "TallyResult": TALLY_RESULTS,
+    "TallyType": TALLY_TYPES,
+    "ProposalType": PROPOSAL_TYPES,
+    "ProposalStatus": PROPOSAL_STATUSES,
+    "ProposalVote": VOTE_CHOICES,
+    "SlashType": SLASH_TYPES,
+    "InternalAddress": tuple(name for name, _, _ in INTERNAL_ADDRESSES),
+}
+
+# Known `StorageProposal` layouts; 0.28 predates IBC PGF targets, so a target is a plain `{target, amount}` struct.
+PROPOSAL_LAYOUTS = ("0.28", "0.31")
+CURRENT_PROPOSAL_LAYOUT = "0.31"
+# `proposal_parse` output fields. `id` and the epochs are `u64` bare JSON integers; set `number_mode="string"` for
+# consumers that read numbers as doubles. Beyond the compiled parser's fields:
+# - `type_index`: Borsh ordinal of `ProposalType`, which may shift if an upgrade reorders the variants
+# - `author_valid`: false, with a null `author`, for an internal or undecodable author, pointing at a shifted layout
+# - `content_bytes` / `content_field_count`: UTF-8 size and entry count of the content
+# - `voting_duration_epochs` / `grace_duration_epochs`: epochs from voting start to end and from end to grace
+# - `status`: as the SDK's `get_status`, `on-going` from `voting_start_epoch` through `voting_end_epoch`
+# - `epoch_boundary`: `voting_start` or `voting_end` when `current_epoch` is exactly that epoch, else null
+# - `data_valid`: whether the type payload decoded in full; a wrong length usually means a `version` mismatch
+# - `is_executable` / `has_code`: whether a default proposal carries a wasm code hash
+# - `voting_power_epoch`: the epoch the ledger tallies stake at, `voting_end_epoch`
+PROPOSAL_FIELDS = ("id", "proposal_type", "type_index", "author", "author_valid", "content", "content_bytes",
+                   "content_field_count", "voting_start_epoch", "voting_end_epoch", "grace_epoch",
+                   "voting_duration_epochs", "grace_duration_epochs", "status", "epoch_boundary", "data", "data_valid",
+                   "is_executable", "has_code", "voting_power_epoch")
+# Output field -> the decoded `StorageProposal` fields it derives from; in `best_effort` mode a null field is only
+# dropped when one of those was not decoded, so nulls of a decoded proposal (`epoch_boundary`, `empty_data="null"`)
+# are kept
+PROPOSAL_FIELD_SOURCES = {
+    "id": ("id",),
+    "proposal_type": ("type_index",),
+    "type_index": ("type_index",),
+    "author": ("author",),
+    "content": ("content",),
+    "content_bytes": ("content",),
+    "content_field_count": ("content",),
+    "voting_start_epoch": ("voting_start_epoch",),
+    "voting_end_epoch": ("voting_end_epoch",),
+    "grace_epoch": ("grace_epoch",),
+    "voting_duration_epochs": ("voting_start_epoch", "voting_end_epoch"),
+    "grace_duration_epochs": ("voting_end_epoch", "grace_epoch"),
+    "status": ("voting_start_epoch", "voting_end_epoch"),
+    "epoch_boundary": ("voting_start_epoch", "voting_end_epoch"),
+    "data": ("type_index",),
+    "data_valid": ("type_index",),
+    "is_executable": ("type_index",),
+    "has_code": ("type_index",),
+    "voting_power_epoch": ("voting_end_epoch",),
+    "content_hash": ("content",),
+    "sort_key": ("voting_start_epoch", "id"),
+}
+EMPTY_DATA_MODES = ("string", "null", "omit")
+VOTE_SORT_KEYS = ("validator", "delegator", "power")
+NESTED_JSON_DEPTH = 3
+NUMBER_MODES = ("number", "string")
+
+
+@dataclass(frozen=True)
+class ParseConfig:
+    """Output defaults shared by the parse functions; frozen, so one instance can be shared across threads.
+
+    - `number_mode`: `number` emits integers as JSON numbers, `string` quotes them for consumers that read numbers
+      as doubles. Decoding into Python objects (`to_value`, `parse_many`) always yields ints.
+    - `pretty`: indent JSON output by two spaces.
+    - `strict`: raise on malformed input; when false, batch functions default to `lenient`, reporting a blob that
+      fails to decode in its place instead of aborting the batch. It only affects failures: `proposal_parse`'s
+      `best_effort`, which changes the shape of successful output too, is never enabled by the config.
+    - `empty_data`: default `proposal_parse` `empty_data` mode.
+    """
+    number_mode: str = "number"
+    pretty: bool = False
+    strict: bool = True
+    empty_data: str = "string"
+
+    def __post_init__(self):
+        if self.number_mode not in NUMBER_MODES:
+            raise ValueError(f"Unknown number_mode '{self.number_mode}', supported: {', '.join(NUMBER_MODES)}")
+        if self.empty_data not in EMPTY_DATA_MODES:
+            raise ValueError(f"Unknown empty_data mode '{self.empty_data}', supported: {', '.join(EMPTY_DATA_MODES)}")
+
+
+_default_config = ParseConfig()
+_scoped_config = contextvars.ContextVar("parse_config", default=None)
+
+
+def get_config() -> ParseConfig:
+    """Return the config in effect: the innermost `use_config` block, else the global default."""
+    return _scoped_config.get() or _default_config
+
+
+def set_config(config: ParseConfig) -> None:
+    """Replace the global default config, e.g. once at startup."""
+    global _default_config
+    _default_config = config
+
+
+def parse_config(**overrides) -> ParseConfig:
+    """Build a config from the one in effect with the given fields changed."""
+    return replace(get_config(), **overrides)
+
+
+@contextlib.contextmanager
+def use_config(config: ParseConfig) -> Iterator[ParseConfig]:
+    """Apply `config` to parse calls within the block; scoped per thread and per asyncio task."""
+    token = _scoped_config.set(config)
+    try:
+        yield config
+    finally:
+        _scoped_config.reset(token)
+
+
+def _quote_ints(value: Any) -> Any:
+    if isinstance(value, dict):
+        return {key: _quote_ints(item) for key, item in value.items()}
+    if isinstance(value, list):
+        return [_quote_ints(item) for item in value]
+    if isinstance(value, int) and not isinstance(value, bool):
+        return str(value)
+    return value
+
+
+def _to_json(value: Any, sort_keys: bool = False) -> str:
+    config = get_config()
+    if config.number_mode == "string":
+        value = _quote_ints(value)
+    if config.pretty:
+        return json.dumps(value, indent=2, ensure_ascii=False, sort_keys=sort_keys)
+    return json.dumps(value, separators=(",", ":"), ensure_ascii=False, sort_keys=sort_keys)
+
+
+def _to_csv(rows: List[Dict[str, Any]], columns: Tuple[str, ...]) -> str:
+    output = io.StringIO()
+    writer = csv.DictWriter(output, fieldnames=columns, extrasaction="ignore")
+    writer.writeheader()
+    writer.writerows(rows)
+    return output.getvalue()
+
+
+def _check_u64(name: str, value: int, positive: bool = False) -> None:
+    if positive and not 0 < value <= U64_MAX:
+        raise ValueError(f"{name} must be a positive u64, got {value}")
+    if not 0 <= value <= U64_MAX:
+        raise ValueError(f"{name} must fit in a u64, got {value}")
+
+
+def _check_denom(denom: int) -> None:
+    if not 0 <= denom <= 255:
+        raise ValueError(f"Denomination must fit in a u8, got {denom}")
+
+
+def _sha256_hex(data: bytes) -> str:
+    """Uppercase hex SHA-256, how the SDK displays its `Hash` type."""
+    return hashlib.sha256(data).hexdigest().upper()
+
+
+def _lenient(lenient: Optional[bool]) -> bool:
+    return not get_config().strict if lenient is None else lenient
+
+
+def _map_in_processes(decode: Callable[[Any], Any], items: List[Any], workers: Optional[int]) -> List[Any]:
+    """Apply `decode` to `items` in order, over `workers` processes when more than one.
+
+    The decoders are pure Python and hold the GIL, so threads would not run them in parallel.
+    """
+    if workers is not None and workers > 1 and len(items) > 1:
+        with ProcessPoolExecutor(max_workers=workers) as pool:
+            return list(pool.map(decode, items, chunksize=max(1, len(items) // (workers * 4))))
+    return [decode(item) for item in items]
+
+
+def enum_variants(type_name: str) -> List[str]:
+    """List the variant strings the parsers can emit for an enum, in Borsh variant order."""
+    if type_name not in ENUM_VARIANTS:
+        raise ValueError(f"Unknown enum type '{type_name}', supported: {', '.join(ENUM_VARIANTS)}")
+    return list(ENUM_VARIANTS[type_name])
+
+
+def epoch_range(start: int, end: int, inclusive: bool = False) -> List[int]:
+    """List the epochs from `start` up to `end`, excluding `end` unless `inclusive`, like the SDK's epoch ranges."""
+    for name, value in (("start", start), ("end", end)):
+        _check_u64(f"{name} epoch", value)
+    return list(range(start, end + 1 if inclusive else end))
+
+
+def epoch_from_height(height: int, first_block_of_epoch0: int, blocks_per_epoch: int) -> int:
+    """Estimate the epoch a block height falls in, assuming every epoch is exactly `blocks_per_epoch` blocks long.
+
+    The SDK starts a new epoch once both `min_num_of_blocks` and `min_duration` have passed and records each epoch's
+    first height, so this is exact only while the block count is the binding condition; when blocks come faster than
+    `min_duration` allows, epochs are longer and the estimate runs ahead. Use the providers' `get_epoch_at_height`
+    for the chain's own answer.
+    """
+    for name, value in (("height", height), ("first_block_of_epoch0", first_block_of_epoch0)):
+        _check_u64(name, value)
+    _check_u64("blocks_per_epoch", blocks_per_epoch, positive=True)
+    if height < first_block_of_epoch0:
+        raise ValueError(f"Height {height} is before the first block of epoch 0 ({first_block_of_epoch0})")
+    return (height - first_block_of_epoch0) // blocks_per_epoch
+
+
+def epoch_parse(data: bytes) -> int:
+    """Decode a Borsh `Epoch` (a `u64`) into an integer."""
+    reader = Reader(data)
+    epoch = reader.u64()
+    reader.finish()
+    return epoch
+
+
+def epochs_parse_batch(data: List[bytes], lenient: Optional[bool] = None) -> List[int]:
+    """Decode a list of Borsh `Epoch` blobs, such as the keys of an epoch-indexed map, into integers in order.
+
+    In `lenient` mode a blob that fails to decode becomes -1, which no `u64` epoch can be.
+    """
+    lenient = _lenient(lenient)
+    epochs = []
+    for blob in data:
+        try:
+            epochs.append(epoch_parse(blob))
+        except ValueError:
+            if not lenient:
+                raise
+            epochs.append(-1)
+    return epochs
+
+
+def bech32m_encode(hrp: str, data: bytes) -> str:
+    """Encode arbitrary bytes as bech32m under `hrp`, using the same variant as Namada addresses."""
+    return bech32m.encode(hrp, bytes(data))
+
+
+def bech32m_decode(value: str) -> Tuple[str, bytes]:
+    """Decode a bech32m string into `(hrp, data)`, raising on checksum or format errors."""
+    return bech32m.decode(value)
+
+
+def _decode_address(data: bytes) -> Address:
+    reader = Reader(data)
+    address = read_address(reader)
+    reader.finish()
+    return address
+
+
+def address_network(data: bytes) -> str:
+    """Return the network prefix a Borsh-encoded address is rendered under.
+
+    Borsh addresses carry no chain identifier and every Namada network uses the `tnam` HRP, so this is always `tnam`
+    and cannot tell networks apart; decoding first makes sure the bytes really are an address.
+    """
+    _decode_address(data)
+    return ADDRESS_HRP
+
+
+def address_parse(data: bytes, expected_network: Optional[str] = None, friendly: bool = False,
+                  registry: Optional[Dict[str, str]] = None) -> str:
+    """Decode an address, raising if `expected_network` is given and is not the `tnam` HRP.
+
+    Every Namada network, mainnet and testnets alike, renders addresses under `tnam` and the Borsh bytes carry no
+    chain identifier, so the check only compares `expected_network` with that constant and cannot catch mainnet bytes
+    fetched from a testnet.
+
+    With `friendly` the output is a JSON `{address, label}` object, where `label` is the internal address name from
+    `enum_variants("InternalAddress")` (e.g. `Governance`) and null for accounts.
+
+    A `registry` maps address strings to labels and also selects the object output; its keys are matched in canonical
+    form, so an uppercase key still matches, and a matching label takes precedence over the internal name. A key that
+    is not a valid address raises.
+    """
+    address = _decode_address(data)
+    if expected_network is not None and expected_network != ADDRESS_HRP:
+        raise ValueError(f"Address belongs to network '{ADDRESS_HRP}', expected '{expected_network}'")
+    if registry is not None:
+        labels = {Address.decode(key).encode(): label for key, label in registry.items()}
+        label = labels.get(address.encode())
+        if label is None and friendly:
+            label = address.internal_name
+        return _to_json({"address": address.encode(), "label": label})
+    if friendly:
+        return _to_json({"address": address.encode(), "label": address.internal_name})
+    return address.encode()
+
+
+def address_raw_bytes(data: bytes) -> bytes:
+    """Return the fixed 21-byte form of a Borsh-encoded address: its string discriminant then its 20-byte hash.
+
+    These are the bytes inside the bech32m string, so they are the same for every encoding of the address, and
+    internal addresses without a hash get a zero hash.
+    """
+    address = _decode_address(data)
+    return bytes([address.discriminant]) + address.hash
+
+
+def address_eq(a: bytes, b: bytes) -> bool:
+    """Compare two Borsh-encoded addresses by their decoded value."""
+    return _decode_address(a) == _decode_address(b)
+
+
+def normalize_address_bytes(data: bytes) -> str:
+    """Return the canonical bech32m form of a Borsh-encoded address, suitable as a map key."""
+    return _decode_address(data).encode()
+
+
+def suggest_address(value: str) -> Optional[str]:
+    """Suggest the address a mistyped `tnam` string most likely meant, or None when there is no safe suggestion.
+
+    Only a single substituted character after the `1` separator is repaired: every one-character change is tried and
+    the suggestion is made only when exactly one of them is a valid address, so a typo is never "corrected" into one
+    of several addresses. A string that is already valid is returned in canonical form; a wrong prefix, a missing or
+    extra character or more than one error gives None.
+    """
+    value = value.strip().lower()
+    try:
+        return Address.decode(value).encode()
+    except ValueError:
+        pass
+    pos = value.rfind("1")
+    if pos < 0 or value[:pos] != ADDRESS_HRP:
+        return None
+    candidates = set()
+    for i in range(pos + 1, len(value)):
+        for char in bech32m.CHARSET:
+            if char == value[i]:
+                continue
+            try:
+                candidates.add(Address.decode(value[:i] + char + value[i + 1:]).encode())
+            except ValueError:
+                continue
+    return candidates.pop() if len(candidates) == 1 else None
+
+
+def payment_address_parse(data: bytes) -> str:
+    """Decode a MASP `PaymentAddress` into its bech32m string (`znam`, or `znampin` when pinned).
+
+    Shielded addresses are not `Address` values, so `address_parse` rejects them. Only the layout is checked: the
+    diversifier and key are not validated as curve points.
+    """
+    reader = Reader(data)
+    raw = reader.read(MASP_PAYMENT_ADDRESS_LEN)
+    pinned = reader.tag(2)
+    reader.finish()
+    return bech32m.encode(MASP_PINNED_PAYMENT_ADDRESS_HRP if pinned else MASP_PAYMENT_ADDRESS_HRP, raw)
+
+
+def viewing_key_parse(data: bytes) -> str:
+    """Decode a MASP `ExtendedViewingKey` into its `zvknam` bech32m string, checking the layout only."""
+    reader = Reader(data)
+    raw = reader.read(MASP_VIEWING_KEY_LEN)
+    reader.finish()
+    return bech32m.encode(MASP_VIEWING_KEY_HRP, raw)
+
+
+def validator_addresses_parse(data: bytes) -> str:
+    """Decode a set of validator addresses into a JSON array of bech32m strings."""
+    reader = Reader(data)
+    addresses = reader.vec(lambda: read_address(reader).encode())
+    reader.finish()
+    return _to_json(addresses)
+
+
+def hash_parse(data: bytes, uppercase: bool = False) -> str:
+    """Decode a bare 32-byte `Hash` into a hex string."""
+    if len(data) != HASH_LEN:
+        raise ValueError(f"Decoding failed: expected a {HASH_LEN}-byte hash, got {len(data)} bytes")
+    digest = bytes(data).hex()
+    return digest.upper() if uppercase else digest
+
+
+def change_parse(data: bytes) -> str:
+    """Decode a signed `token::Change` (a two's-complement `I256`) into a signed integer string."""
+    reader = Reader(data)
+    change = reader.i256()
+    reader.finish()
+    return str(change)
+
+
+def token_amount_parse(data: bytes, denom: int = 0, group: bool = False) -> str:
+    """Decode a `token::Amount` into a display string, the raw integer by default.
+
+    `denom` places the decimal point (trailing zeros dropped) and `group` separates the integer part in thousands with
+    `,`, regardless of locale, e.g. `1,234,567.5`.
+    """
+    _check_denom(denom)
+    reader = Reader(data)
+    amount = format_decimal(reader.u256(), denom)
+    reader.finish()
+    if group:
+        integer, dot, fraction = amount.partition(".")
+        amount = f"{int(integer):,}{dot}{fraction}"
+    return amount
+
+
+def amount_encode(value: str, denom: int) -> bytes:
+    """Encode a decimal string as a Borsh `token::Amount` scaled by `denom`, the inverse of `token_amount_parse`.
+
+    The value must be non-negative with at most `denom` fractional digits, and the scaled amount must fit in a `U256`.
+    """
+    _check_denom(denom)
+    match = DEC_PATTERN.match(value)
+    if not match or match.group(1):
+        raise ValueError(f"Invalid amount string: {value!r}")
+    _, integer, fraction = match.groups()
+    fraction = fraction or ""
+    if len(fraction) > denom:
+        raise ValueError(f"Amount {value} has more than {denom} fractional digits")
+    raw = int(integer + fraction.ljust(denom, "0"))
+    if raw >= 2 ** 256:
+        raise ValueError(f"Amount {value} overflows a token amount")
+    return raw.to_bytes(32, "little")
+
+
+def _read_address_amount(reader: Reader) -> Dict[str, str]:
+    return {"address": read_address(reader).encode(), "amount": str(reader.u256())}
+
+
+def address_amount_pair_parse(data: bytes) -> str:
+    """Decode an `(Address, token::Amount)` tuple into `{address, amount}`."""
+    reader = Reader(data)
+    pair = _read_address_amount(reader)
+    reader.finish()
+    return _to_json(pair)
+
+
+def address_amount_pairs_parse(data: bytes) -> str:
+    """Decode a `Vec<(Address, token::Amount)>` into `[{address, amount}]`."""
+    reader = Reader(data)
+    pairs = reader.vec(lambda: _read_address_amount(reader))
+    reader.finish()
+    return _to_json(pairs)
+
+
+def _decode_address_amount_map(data: bytes) -> Dict[str, int]:
+    reader = Reader(data)
+    amounts = {}
+    for _ in range(reader.u32()):
+        address = read_address(reader)
+        amounts[address] = reader.u256()
+    reader.finish()
+    return {address.encode(): amounts[address] for address in sorted(amounts, key=address_order_key)}
+
+
+def balances_parse(data: bytes, denom: Optional[int] = None) -> str:
+    """Decode a `BTreeMap<Address, token::Amount>` of balances into `{address: amount}` in map order.
+
+    Map order is the SDK's address ordering (established, implicit, then internal addresses), not the order of the
+    address strings. Amounts are raw integer strings, or scaled by `denom` when given.
+    """
+    if denom is not None:
+        _check_denom(denom)
+    return _to_json({address: format_decimal(amount, denom) if denom is not None else str(amount)
+                     for address, amount in _decode_address_amount_map(data).items()})
+
+
+def gas_limit_parse(data: bytes) -> int:
+    """Decode a `u64` gas quantity, such as a transaction's `GasLimit` or the `max_block_gas` parameter."""
+    reader = Reader(data)
+    gas = reader.u64()
+    reader.finish()
+    return gas
+
+
+def gas_cost_parse(data: bytes) -> str:
+    """Decode the `gas_cost` parameter into `{token: amount}`.
+
+    The parameter is a `BTreeMap<Address, token::Amount>` of the minimum price per gas unit in each fee token; amounts
+    are raw integer strings.
+    """
+    return _to_json({token: str(amount) for token, amount in _decode_address_amount_map(data).items()})
+
+
+def delegations_parse(data: bytes) -> str:
+    """Decode an account's delegations into `[{validator, amount}]`.
+
+    Accepts both the validator set (`HashSet<Address>`, amounts are null) and the bonded amount map
+    (`HashMap<Address, token::Amount>`); a map entry is always longer than an address so the shapes cannot be confused.
+    """
+    try:
+        reader = Reader(data)
+        delegations = reader.vec(lambda: {"validator": read_address(reader).encode(), "amount": str(reader.u256())})
+        reader.finish()
+    except ValueError:
+        reader = Reader(data)
+        delegations = reader.vec(lambda: {"validator": read_address(reader).encode(), "amount": None})
+        reader.finish()
+    return _to_json(delegations)
+
+
+def _decode_metadata(data: bytes) -> Dict[str, Optional[str]]:
+    reader = Reader(data)
+    metadata = {"email": reader.string()}
+    for field in METADATA_FIELDS[1:]:
+        metadata[field] = reader.option(reader.string)
+    reader.finish()
+    return metadata
+
+
+def _is_safe_url(value: str) -> bool:
+    if any(x in value for x in "<>\"'` ") or any(ord(x) < 32 for x in value):
+        return False
+    parsed = urlparse(value if "://" in value else f"https://{value}")
+    try:
+        parsed.port
+    except ValueError:
+        return False
+    return parsed.scheme in ("http", "https") and bool(HOSTNAME_PATTERN.match(parsed.hostname or ""))
+
+
+def validator_metadata_parse(data: bytes, sanitize: bool = False) -> str:
+    """Decode `ValidatorMetaData` into a JSON object.
+
+    The fields are user controlled; with `sanitize` an email that does not look like one, a website or avatar that is
+    not an http(s) URL (a bare domain is accepted) and a discord handle with unexpected characters are nulled, and the
+    nulled field names are listed under `sanitized`. The description is left to the renderer to escape.
+    """
+    metadata = _decode_metadata(data)
+    if sanitize:
+        checks = {
+            "email": lambda v: bool(EMAIL_PATTERN.match(v)),
+            "website": _is_safe_url,
+            "discord_handle": lambda v: bool(DISCORD_HANDLE_PATTERN.match(v)),
+            "avatar": _is_safe_url,
+        }
+        metadata["sanitized"] = []
+        for field, check in checks.items():
+            if metadata[field] is not None and not check(metadata[field]):
+                metadata[field] = None
+                metadata["sanitized"].append(field)
+    return _to_json(metadata)
+
+
+def validate_metadata_lengths(metadata_json: str, limits_json: str) -> str:
+    """Check a prospective `ValidatorMetaData` JSON object against per-field length limits before submitting it.
+
+    Both objects are keyed by `METADATA_FIELDS`; `limits_json` maps a field to its maximum length, counted in UTF-8
+    bytes as the ledger's `String::len` does. The result is `[{field, length, limit}]` for the fields over their limit,
+    in `METADATA_FIELDS` order; absent, null and empty fields never violate.
+    """
+    metadata = json.loads(metadata_json)
+    limits = json.loads(limits_json)
+    for name, value in (("metadata", metadata), ("limits", limits)):
+        if not isinstance(value, dict):
+            raise ValueError(f"{name} must be a JSON object")
+        unknown = [field for field in value if field not in METADATA_FIELDS]
+        if unknown:
+            raise ValueError(f"Unknown metadata fields in {name}: {', '.join(unknown)}; "
+                             f"supported: {', '.join(METADATA_FIELDS)}")
+    violations = []
+    for field in METADATA_FIELDS:
+        value = metadata.get(field)
+        if value is not None and not isinstance(value, str):
+            raise ValueError(f"Metadata field '{field}' must be a string or null")
+        limit = limits.get(field)
+        if limit is None:
+            continue
+        if isinstance(limit, bool) or not isinstance(limit, int) or limit < 0:
+            raise ValueError(f"Limit for '{field}' must be a non-negative integer, got {limit!r}")
+        length = len(value.encode("utf-8")) if value else 0
+        if length > limit:
+            violations.append({"field": field, "length": length, "limit": limit})
+    return _to_json(violations)
+
+
+def validator_metadata_parse_batch(addresses: List[str], blobs: List[bytes], sanitize: bool = False,
+                                   lenient: Optional[bool] = None) -> str:
+    """Decode a page of `ValidatorMetaData` blobs into `[{address, metadata}]`, pairing each with its address.
+
+    `sanitize` is applied as in `validator_metadata_parse`. In `lenient` mode a blob that fails to decode gets a null
+    `metadata` and an `error`.
+    """
+    lenient = _lenient(lenient)
+    if len(addresses) != len(blobs):
+        raise ValueError(f"Got {len(addresses)} addresses for {len(blobs)} metadata blobs")
+    entries = []
+    for address, blob in zip(addresses, blobs):
+        try:
+            entries.append({"address": address, "metadata": json.loads(validator_metadata_parse(blob, sanitize))})
+        except ValueError as e:
+            if not lenient:
+                raise
+            entries.append({"address": address, "metadata": None, "error": str(e)})
+    return _to_json(entries)
+
+
+def _decode_commission_pair(data: bytes) -> Tuple[int, int]:
+    reader = Reader(data)
+    pair = (reader.i256(), reader.i256())
+    reader.finish()
+    return pair
+
+
+def _format_percent(value: int, places: Optional[int]) -> str:
+    # A `Dec` ratio times 100 is the same integer with two fewer decimal places
+    scale = DEC_PRECISION - 2
+    if places is None:
+        return format_decimal(value, scale)
+    sign = "-" if value < 0 else ""
+    integer, fraction = divmod(abs(value) // 10 ** (scale - places), 10 ** places)
+    return f"{sign}{integer}.{str(fraction).rjust(places, '0')}" if places else f"{sign}{integer}"
+
+
+def commission_pair_parse(data: bytes, as_percent: bool = False, places: Optional[int] = None) -> str:
+    """Decode a `CommissionPair`, matching the compiled `rust_py.commission_pair_parse` by default.
+
+    With `as_percent` both rates are multiplied by 100 exactly; `places` then fixes the number of decimal places,
+    truncating the rest, instead of dropping trailing zeros.
+    """
+    if places is not None and not 0 <= places <= DEC_PRECISION - 2:
+        raise ValueError(f"places must be between 0 and {DEC_PRECISION - 2}, got {places}")
+    rate, max_change = _decode_commission_pair(data)
+    if as_percent:
+        return _to_json({"commission_rate": _format_percent(rate, places),
+                         "max_commission_change_per_epoch": _format_percent(max_change, places)})
+    return _to_json({"commission_rate": format_decimal(rate, DEC_PRECISION),
+                     "max_commission_change_per_epoch": format_decimal(max_change, DEC_PRECISION)})
+
+
+def commission_delta(old: bytes, new: bytes) -> str:
+    """Compare two `CommissionPair`s into `{old_rate, new_rate, change, exceeds_max_change}`.
+
+    `change` is `new - old` commission rate as a signed `Dec` string. `exceeds_max_change` tells whether its absolute
+    value is larger than the `max_commission_change_per_epoch` of `new`; the limit cannot change after a validator is
+    created, so either pair's limit is the one in force.
+    """
+    old_rate, _ = _decode_commission_pair(old)
+    new_rate, max_change = _decode_commission_pair(new)
+    change = new_rate - old_rate
+    return _to_json({"old_rate": format_decimal(old_rate, DEC_PRECISION),
+                     "new_rate": format_decimal(new_rate, DEC_PRECISION),
+                     "change": format_decimal(change, DEC_PRECISION),
+                     "exceeds_max_change": abs(change) > max_change})
+
+
+def dec_parse(data: bytes) -> str:
+    """Decode a Borsh-encoded `Dec` into its decimal string, trailing zeros dropped."""
+    reader = Reader(data)
+    value = reader.i256()
+    reader.finish()
+    return format_decimal(value, DEC_PRECISION)
+
+
+def dec_to_bps(data: bytes) -> str:
+    """Decode a Borsh-encoded `Dec` rate into basis points, e.g. `0.0525` into `525`.
+
+    A `Dec` times 10000 is the same integer with four fewer decimal places, so the result is exact: an integer string
+    when the rate is a whole number of basis points, else a decimal string with trailing zeros dropped.
+    """
+    reader = Reader(data)
+    value = reader.i256()
+    reader.finish()
+    return format_decimal(value, DEC_PRECISION - 4)
+
+
+def dec_encode(value: str) -> bytes:
+    """Encode a decimal string as a Borsh `Dec`, the inverse of `dec_parse`.
+
+    At most 12 fractional digits are accepted, as the SDK does not round; the result must fit in the `I256` backing
+    the `Dec`.
+    """
+    match = DEC_PATTERN.match(value)
+    if not match:
+        raise ValueError(f"Invalid decimal string: {value!r}")
+    sign, integer, fraction = match.groups()
+    fraction = fraction or ""
+    if len(fraction) > DEC_PRECISION:
+        raise ValueError(f"Decimal {value} has more than {DEC_PRECISION} fractional digits")
+    raw = int(integer + fraction.ljust(DEC_PRECISION, "0")) * (-1 if sign else 1)
+    if not -2 ** 255 <= raw < 2 ** 255:
+        raise ValueError(f"Decimal {value} is out of range for a Dec")
+    return raw.to_bytes(32, "little", signed=True)
+
+
+def dec_compare(a: bytes, b: bytes) -> int:
+    """Compare two Borsh-encoded `Dec` values exactly, returning -1, 0 or 1 like a classic comparator.
+
+    Use with `functools.cmp_to_key` to rank commission or reward rates without going through floats.
+    """
+    values = []
+    for data in (a, b):
+        reader = Reader(data)
+        values.append(reader.i256())
+        reader.finish()
+    return (values[0] > values[1]) - (values[0] < values[1])
+
+
+def validator_commission_history_parse(history: Dict[int, bytes]) -> str:
+    """Decode an epoch -> `CommissionPair` map into `{history, violations}`.
+
+    Commission rates are epoched data that is only written on a change, so consecutive entries are compared even when
+    epochs in between are missing. A change larger than the `max_commission_change_per_epoch` in force before it is
+    listed under `violations` with the absolute `change` and the `max_change` it exceeded.
+    """
+    entries = []
+    violations = []
+    previous = None
+    for epoch in sorted(history):
+        _check_u64("Epoch", epoch)
+        rate, max_change = _decode_commission_pair(history[epoch])
+        entries.append({"epoch": epoch, "commission_rate": format_decimal(rate, DEC_PRECISION),
+                        "max_commission_change_per_epoch": format_decimal(max_change, DEC_PRECISION)})
+        if previous is not None:
+            previous_epoch, previous_rate, previous_max_change = previous
+            change = abs(rate - previous_rate)
+            if change > previous_max_change:
+                violations.append({"epoch": epoch, "previous_epoch": previous_epoch,
+                                   "change": format_decimal(change, DEC_PRECISION),
+                                   "max_change": format_decimal(previous_max_change, DEC_PRECISION)})
+        previous = (epoch, rate, max_change)
+    return _to_json({"history": entries, "violations": violations})
+
+
+def validator_uptime_parse(data: bytes, window_len: int) -> str:
+    """Decode a validator's liveness counter into `{missed_blocks, window_len}`.
+
+    PoS keeps a `u64` sum of the votes a validator missed over the last `liveness_window_check` blocks; the storage
+    value carries no window, so `window_len` is that PoS parameter as queried by the caller.
+    """
+    _check_u64("window_len", window_len, positive=True)
+    reader = Reader(data)
+    missed = reader.u64()
+    reader.finish()
+    return _to_json({"missed_blocks": missed, "window_len": window_len})
+
+
+def slashes_parse(data: bytes, group_by_epoch: bool = False) -> str:
+    """Decode a validator's `Vec<Slash>` into `[{epoch, block_height, type, rate}]`, in storage order.
+
+    With `group_by_epoch` the slashes are keyed by the epoch of the infraction instead, as
+    `{epoch: {count, total_rate, slashes}}` in ascending epoch order; `total_rate` is the exact `Dec` sum of the
+    rates, which is not capped at 1 the way PoS caps the cubic slash rate it actually applies.
+    """
+    reader = Reader(data)
+    slashes = reader.vec(lambda: {"epoch": reader.u64(), "block_height": reader.u64(),
+                                  "type": SLASH_TYPES[reader.tag(len(SLASH_TYPES))], "rate": reader.i256()})
+    reader.finish()
+    if not group_by_epoch:
+        return _to_json([{**slash, "rate": format_decimal(slash["rate"], DEC_PRECISION)} for slash in slashes])
+    groups = {}
+    for slash in slashes:
+        groups.setdefault(slash["epoch"], []).append(slash)
+    return _to_json({
+        str(epoch): {
+            "count": len(groups[epoch]),
+            "total_rate": format_decimal(sum(slash["rate"] for slash in groups[epoch]), DEC_PRECISION),
+            "slashes": [{**slash, "rate": format_decimal(slash["rate"], DEC_PRECISION)} for slash in groups[epoch]],
+        }
+        for epoch in sorted(groups)
+    })
+
+
+def jailed_since_parse(states: Dict[int, bytes]) -> Optional[int]:
+    """Return the epoch a validator was jailed at from an epoch -> `ValidatorState` history, or None if not jailed.
+
+    PoS does not store the jailing epoch on its own, so this is the first epoch of the run of `Jailed` states that
+    reaches the latest epoch given; the history must go back far enough to include the epoch before the jailing.
+    """
+    jailed_since = None
+    for epoch in sorted(states):
+        reader = Reader(states[epoch])
+        state = VALIDATOR_STATES[reader.tag(len(VALIDATOR_STATES))]
+        reader.finish()
+        if state != "Jailed":
+            jailed_since = None
+        elif jailed_since is None:
+            jailed_since = epoch
+    return jailed_since
+
+
+def _decode_weighted_validators(data: bytes) -> List[Dict[str, Any]]:
+    reader = Reader(data)
+    validators = reader.vec(lambda: (reader.u256(), read_address(reader)))
+    reader.finish()
+    # `WeightedValidator` derives `Ord` over the stake and then the address, using the SDK's address ordering
+    validators = sorted(set(validators), key=lambda validator: (validator[0], address_order_key(validator[1])))
+    return [{"address": address.encode(), "bonded_stake": stake} for stake, address in validators]
+
+
+def weighted_validators_parse(data: bytes, include_metrics: bool = False) -> str:
+    """Decode a `BTreeSet<WeightedValidator>` (consensus or below-capacity set) into `[{address, bonded_stake}]`.
+
+    Validators come in set order: lowest stake first, equal stakes in the SDK's address ordering rather than by
+    address string. With `include_metrics` the output is `{validators, total_stake, nakamoto_coefficient}` instead,
+    the validators ordered highest stake first (equal stakes by address) and each given the `cumulative_share` of the
+    total held by it and those before it, a `Dec` string rounded toward zero. `nakamoto_coefficient` is the number of
+    validators in that order needed to hold more than a third of the total stake, the consensus halting threshold.
+    Shares and the coefficient are null when the total stake is zero.
+    """
+    validators = _decode_weighted_validators(data)
+    if not include_metrics:
+        for validator in validators:
+            validator["bonded_stake"] = str(validator["bonded_stake"])
+        return _to_json(validators)
+    validators.sort(key=lambda validator: (-validator["bonded_stake"], validator["address"]))
+    total = sum(validator["bonded_stake"] for validator in validators)
+    cumulative = 0
+    nakamoto = None
+    for count, validator in enumerate(validators, 1):
+        cumulative += validator["bonded_stake"]
+        if nakamoto is None and total and 3 * cumulative > total:
+            nakamoto = count
+        validator["bonded_stake"] = str(validator["bonded_stake"])
+        validator["cumulative_share"] = _ratio_string(cumulative, total)
+    return _to_json({"validators": validators, "total_stake": str(total), "nakamoto_coefficient": nakamoto})
+
+
+def full_validator_set_parse(consensus: bytes, below_capacity: bytes) -> str:
+    """Merge the consensus and below-capacity sets into `[{address, tier, bonded_stake}]`, highest stake first.
+
+    Validators with equal stake are ordered by address; an empty input, e.g. a missing below-capacity set, contributes
+    no validators.
+    """
+    validators = []
+    for tier, data in zip(VALIDATOR_SET_TIERS, (consensus, below_capacity)):
+        for validator in _decode_weighted_validators(data) if data else []:
+            validators.append({"address": validator["address"], "tier": tier,
+                               "bonded_stake": validator["bonded_stake"]})
+    validators.sort(key=lambda v: (-v["bonded_stake"], v["address"]))
+    for validator in validators:
+        validator["bonded_stake"] = str(validator["bonded_stake"])
+    return _to_json(validators)
+
+
+def weighted_validators_parse_csv(data: bytes) -> str:
+    """Decode a `BTreeSet<WeightedValidator>` into CSV with an `address,bonded_stake` header row."""
+    return _to_csv(json.loads(weighted_validators_parse(data)), ("address", "bonded_stake"))
+
+
+def _validator_tiers(snapshot: Dict[str, bytes]) -> Dict[str, Tuple[str, int]]:
+    tiers = {}
+    for tier, data in snapshot.items():
+        if tier not in VALIDATOR_SET_TIERS:
+            raise ValueError(f"Unknown validator set tier '{tier}', supported: {', '.join(VALIDATOR_SET_TIERS)}")
+        for validator in _decode_weighted_validators(data) if data else []:
+            tiers[validator["address"]] = (tier, validator["bonded_stake"])
+    return tiers
+
+
+def validator_set_update_parse(previous: Dict[str, bytes], current: Dict[str, bytes]) -> str:
+    """Diff two validator set snapshots into `[{address, from, to, bonded_stake}]` transitions.
+
+    The SDK does not store set updates, so each snapshot maps a tier (`Consensus`/`BelowCapacity`) to the weighted
+    validator set queried for that epoch. `from`/`to` are null when a validator enters or leaves both tiers, and
+    `bonded_stake` is the current stake, or the last known one for validators that left.
+    """
+    before = _validator_tiers(previous)
+    after = _validator_tiers(current)
+    updates = []
+    for address in sorted(set(before) | set(after)):
+        old_tier, old_stake = before.get(address, (None, None))
+        new_tier, new_stake = after.get(address, (None, None))
+        if old_tier != new_tier:
+            stake = new_stake if new_tier is not None else old_stake
+            updates.append({"address": address, "from": old_tier, "to": new_tier, "bonded_stake": str(stake)})
+    return _to_json(updates)
+
+
+def zip_addresses_states(addrs: Union[bytes, List[str]], states_json: str) -> str:
+    """Join a validator address set with separately queried states into `[{address, state}]`.
+
+    `addrs` is the encoded address set or its already decoded addresses. `states_json` maps bech32m addresses to a
+    validator state name; validators missing from it get a null state.
+    """
+    states = json.loads(states_json)
+    if not isinstance(states, dict):
+        raise ValueError("states_json must be a JSON object mapping addresses to states")
+    for address, state in states.items():
+        if state is not None and state not in VALIDATOR_STATES:
+            raise ValueError(f"Unknown validator state for {address}: {state}")
+    if isinstance(addrs, bytes):
+        addrs = json.loads(validator_addresses_parse(addrs))
+    return _to_json([{"address": address, "state": states.get(address)} for address in addrs])
+
+
+def _parse_ratio(value: str, name: str) -> Fraction:
+    try:
+        decimal = Decimal(value)
+    except (InvalidOperation, ValueError, TypeError):
+        raise ValueError(f"{name} must be a decimal string, got {value!r}")
+    if not decimal.is_finite():
+        raise ValueError(f"{name} must be a finite decimal, got {value!r}")
+    ratio = Fraction(decimal)
+    if not 0 <= ratio <= 1:
+        raise ValueError(f"{name} must be a ratio between 0 and 1, got {value}")
+    return ratio
+
+
+def _decode_proposal_result(data: bytes) -> Dict[str, Any]:
+    reader = Reader(data)
+    result = {
+        "result": TALLY_RESULTS[reader.tag(len(TALLY_RESULTS))],
+        "tally_type": TALLY_TYPES[reader.tag(len(TALLY_TYPES))],
+        "total_voting_power": reader.u256(),
+        "total_yay_power": reader.u256(),
+        "total_nay_power": reader.u256(),
+        "total_abstain_power": reader.u256(),
+    }
+    reader.finish()
+    return result
+
+
+def _threshold_power(result: Dict[str, Any]) -> Optional[int]:
+    total = result["total_voting_power"]
+    if total == 0:
+        return None
+    nay = result["total_nay_power"]
+    others = nay + result["total_abstain_power"]
+    if result["tally_type"] == "TwoThirds":
+        return max(2 * nay, -((3 * others - 2 * total) // 3), 0)
+    if result["tally_type"] == "OneHalfOverOneThird":
+        return max(nay + 1, -((3 * others - total) // 3))
+    # Passing only fails with a one-third turnout and a nay majority. Yay power below nay can pass on a low turnout,
+    # but adding yay raises the turnout too, so only yay at least equal to nay passes whatever is added
+    return nay
+
+
+# `(key, field number, type)` of `proposal_result_parse_proto`; new keys take a new number and numbers are never reused
+PROPOSAL_RESULT_PROTO_FIELDS = (
+    ("margin", 1, "string"),
+    ("margin_ratio", 2, "string"),
+    ("provisional", 3, "bool"),
+    ("result", 4, "string"),
+    ("tally_type", 5, "string"),
+    ("threshold_power", 6, "string"),
+    ("total_abstain_power", 7, "string"),
+    ("total_nay_power", 8, "string"),
+    ("total_voting_power", 9, "string"),
+    ("total_yay_power", 10, "string"),
+)
+PROPOSAL_RESULT_PROTO = 'syntax = "proto3";\n\nmessage ProposalResult {\n%s}\n' % "".join(
+    f"  optional {proto_type} {key} = {number};\n" for key, number, proto_type in PROPOSAL_RESULT_PROTO_FIELDS)
+
+
+def proposal_result_parse(data: bytes) -> str:
+    """Decode a stored proposal result.
+
+    The chain only stores a result once voting has ended and the proposal was tallied, and `TallyResult` is
+    either `Passed` or `Rejected`. An empty value therefore means the tally has not happened yet, which is
+    reported as `provisional` with a null outcome instead of being mistaken for a rejection.
+
+    `margin` is yay minus nay power as a signed integer string and `margin_ratio` that margin over the total voting
+    power as a `Dec` string, null when the total is zero.
+
+    `threshold_power` is the smallest yay power from which the tally type's rule (see `TALLY_RULES`) passes given the
+    recorded nay and abstain power, for that value and every larger one, as an integer string, null when the total is
+    zero. It covers both the turnout and the majority condition, so compare it with `total_yay_power` for a progress
+    bar.
+
+    The SDK's `ProposalResult` has kept the same field names in every layout this module reads, and the compiled
+    parser always emitted them, so there are no legacy key names to alias.
+    """
+    return _to_json(_proposal_result_fields(data), sort_keys=True)
+
+
+def _proposal_result_fields(data: bytes) -> Dict[str, Any]:
+    if not data:
+        return {"provisional": True, "result": None, "tally_type": None, "total_abstain_power": None,
+                "total_nay_power": None, "total_voting_power": None, "total_yay_power": None,
+                "margin": None, "margin_ratio": None, "threshold_power": None}
+    result = _decode_proposal_result(data)
+    threshold = _threshold_power(result)
+    result["threshold_power"] = str(threshold) if threshold is not None else None
+    margin = result["total_yay_power"] - result["total_nay_power"]
+    result["margin_ratio"] = _ratio_string(margin, result["total_voting_power"])
+    result["margin"] = str(margin)
+    for key in TALLY_POWER_FIELDS:
+        result[key] = str(result[key])
+    result["provisional"] = False
+    return result
+
+
+def _proto_varint(value: int) -> bytes:
+    out = bytearray()
+    while value > 0x7f:
+        out.append(value & 0x7f | 0x80)
+        value >>= 7
+    out.append(value)
+    return bytes(out)
+
+
+def proposal_result_parse_proto(data: bytes) -> bytes:
+    """Decode a stored proposal result into a serialized `ProposalResult` message of `PROPOSAL_RESULT_PROTO`.
+
+    The message mirrors the `proposal_result_parse` JSON: one field per key, numbered as in
+    `PROPOSAL_RESULT_PROTO_FIELDS`, with the same string values (powers stay decimal strings, as a U256 does not
+    fit a protobuf integer). Every field is `optional`, so a JSON null is an absent field and `provisional: false` is
+    still sent.
+    """
+    result = _proposal_result_fields(data)
+    out = bytearray()
+    for key, number, proto_type in PROPOSAL_RESULT_PROTO_FIELDS:
+        value = result[key]
+        if value is None:
+            continue
+        if proto_type == "bool":
+            out += _proto_varint(number << 3) + _proto_varint(int(value))
+        else:
+            raw = value.encode("utf-8")
+            out += _proto_varint(number << 3 | 2) + _proto_varint(len(raw)) + raw
+    return bytes(out)
+
+
+def _ratio_string(numerator: Optional[int], denominator: Optional[int]) -> Optional[str]:
+    if numerator is None or not denominator:
+        return None
+    sign = -1 if numerator < 0 else 1
+    return format_decimal(sign * (abs(numerator) * 10 ** DEC_PRECISION // denominator), DEC_PRECISION)
+
+
+def proposal_result_parse_full(data: bytes, total_supply: Optional[str] = None, count_abstain: bool = True) -> str:
+    """Decode a stored proposal result with integer powers and derived ratios in one object.
+
+    Keys match `proposal_result_parse`, with the powers, `margin` and `threshold_power` as JSON integers, plus ratios
+    as `Dec` strings (12 decimals, rounded toward zero), each null when its denominator is zero:
+    - `yay_ratio` / `nay_ratio` / `abstain_ratio`: share of `total_voting_power`;
+    - `turnout`: yay + nay + abstain power over `total_voting_power`;
+    - `supply_turnout`: the same voted power over the integer `total_supply`, null when it is not given.
+
+    `count_abstain=False` leaves abstain power out of both turnouts; the default matches the SDK, whose tally rules
+    count abstaining as voting. `threshold_power` always follows the SDK.
+    """
+    supply = None
+    if total_supply is not None:
+        if not total_supply.isdigit():
+            raise ValueError(f"total_supply must be an unsigned integer string, got {total_supply!r}")
+        supply = int(total_supply)
+    if not data:
+        output = dict.fromkeys(("result", "tally_type") + TALLY_POWER_FIELDS)
+        output["provisional"] = True
+        voted = total = output["margin"] = output["threshold_power"] = None
+    else:
+        output = _decode_proposal_result(data)
+        output["provisional"] = False
+        voted = output["total_yay_power"] + output["total_nay_power"]
+        if count_abstain:
+            voted += output["total_abstain_power"]
+        total = output["total_voting_power"]
+        output["margin"] = output["total_yay_power"] - output["total_nay_power"]
+        output["threshold_power"] = _threshold_power(output)
+    output["margin_ratio"] = _ratio_string(output["margin"], total)
+    for key, field in (("yay_ratio", "total_yay_power"), ("nay_ratio", "total_nay_power"),
+                       ("abstain_ratio", "total_abstain_power")):
+        output[key] = _ratio_string(output[field], total)
+    output["turnout"] = _ratio_string(voted, total)
+    output["supply_turnout"] = _ratio_string(voted, supply)
+    return _to_json(output, sort_keys=True)
+
+
+def result_diff(a: bytes, b: bytes) -> str:
+    """Compare two stored proposal results, e.g. a provisional tally `a` against the final one `b`.
+
+    Power deltas are `b - a` as signed integer strings; `outcome_flipped` tells whether the result changed. An empty
+    side is a proposal not tallied yet, as in `proposal_result_parse`: its `result` and `tally_type` are null and so
+    are `outcome_flipped` and every delta.
+    """
+    empty = dict.fromkeys(("result", "tally_type") + TALLY_POWER_FIELDS)
+    before = _decode_proposal_result(a) if a else empty
+    after = _decode_proposal_result(b) if b else empty
+    tallied = bool(a and b)
+    return _to_json({
+        "result": {"before": before["result"], "after": after["result"]},
+        "outcome_flipped": before["result"] != after["result"] if tallied else None,
+        "tally_type": {"before": before["tally_type"], "after": after["tally_type"]},
+        "deltas": {field: str(after[field] - before[field]) if tallied else None for field in TALLY_POWER_FIELDS},
+    })
+
+
+def supply_parse(data: bytes, denom: int) -> str:
+    """Decode a token total supply into its raw integer and its value scaled by the token's denomination."""
+    _check_denom(denom)
+    reader = Reader(data)
+    raw = reader.u256()
+    reader.finish()
+    return _to_json({"raw": str(raw), "amount": format_decimal(raw, denom)})
+
+
+def check_quorum(result: bytes, min_quorum: str, count_abstain: bool = True) -> bool:
+    """Check whether a stored proposal result met quorum.
+
+    `min_quorum` is a ratio `Dec` string (e.g. `"0.33"`), not an absolute power: turnout is yay + nay + abstain power
+    over the total voting power, compared exactly. Abstaining counts as participating in the SDK's tally rules;
+    `count_abstain=False` applies a quorum definition that only counts yay and nay power. An empty `result` is a
+    proposal not tallied yet, which has no turnout to check and raises.
+    """
+    threshold = _parse_ratio(min_quorum, "min_quorum")
+    if not result:
+        raise ValueError("Proposal has not been tallied yet: the stored result is empty")
+    tally = _decode_proposal_result(result)
+    if tally["total_voting_power"] == 0:
+        return threshold == 0
+    turnout = tally["total_yay_power"] + tally["total_nay_power"]
+    if count_abstain:
+        turnout += tally["total_abstain_power"]
+    return Fraction(turnout, tally["total_voting_power"]) >= threshold
+
+
+# Proposal batches repeat the same authors, stewards and PGF targets, and bech32m encoding dominates decode time
+@functools.lru_cache(maxsize=4096)
+def _address_string(address: Address) -> str:
+    return address.encode()
+
+
+def tally_explain(data: bytes) -> str:
+    """Explain a stored proposal result in plain English.
+
+    The text gives the outcome, the rule of its tally type from `TALLY_RULES` and the power figures it was decided
+    on; an empty value explains that the proposal has not been tallied yet.
+    """
+    if not data:
+        return "Pending: the proposal has not been tallied yet; a result is stored once voting has ended."
+    tally = _decode_proposal_result(data)
+    total = tally["total_voting_power"]
+    voted = tally["total_yay_power"] + tally["total_nay_power"] + tally["total_abstain_power"]
+    turnout = f" ({_format_percent(voted * 10 ** DEC_PRECISION // total, 2)}%)" if total else ""
+    return (f"{tally['result']}: {TALLY_RULES[tally['tally_type']]}. {voted} of {total} voting power voted{turnout}, "
+            f"with {tally['total_yay_power']} yay, {tally['total_nay_power']} nay and "
+            f"{tally['total_abstain_power']} abstain.")
+
+
+def _read_pgf_target(reader: Reader, version: str) -> Tuple:
+    if version == "0.28" or reader.tag(2) == 0:
+        return 0, _address_string(read_address(reader)), reader.u256()
+    return 1, reader.string(), reader.u256(), reader.ibc_identifier("port ID", 2, 128), \
+        reader.ibc_identifier("channel ID", 8, 64)
+
+
+def _read_pgf_action(reader: Reader, version: str) -> Tuple:
+    if reader.tag(2) == 0:
+        return 0, reader.tag(len(ADD_REMOVE)), _read_pgf_target(reader, version)
+    return 1, 0, _read_pgf_target(reader, version)
+
+
+def _read_content(reader: Reader, errors: str) -> Dict[str, Any]:
+    content = {}
+    for _ in range(reader.u32()):
+        key = reader.string(errors)
+        content[key] = reader.string(errors)
+    return {"content": dict(sorted(content.items()))}
+
+
+def _read_proposal_type(reader: Reader, version: str) -> Dict[str, Any]:
+    type_index = reader.tag(len(PROPOSAL_TYPES))
+    if type_index == 0:
+        code_hash = reader.option(lambda: reader.read(HASH_LEN))
+        payload = code_hash
+        data_str = f"Hash: {code_hash.hex().upper()}" if code_hash is not None else ""
+    elif type_index == 1:
+        payload = sorted(set(reader.vec(lambda: (reader.tag(len(ADD_REMOVE)), _address_string(read_address(reader))))))
+        data_str = ", ".join(f"{ADD_REMOVE[op]}({address})" for op, address in payload)
+    else:
+        payload = sorted(set(reader.vec(lambda: _read_pgf_action(reader, version))))
+        data_str = ", ".join(f"{'Retro' if kind else ADD_REMOVE[op]}({target[1]})" for kind, op, target in payload)
+    return {"type_index": type_index, "proposal_type": PROPOSAL_TYPES[type_index], "payload": payload,
+            "data": data_str}
+
+
+def _decode_proposal(data: bytes, version: str = CURRENT_PROPOSAL_LAYOUT, replace_invalid_utf8: bool = False,
+                     best_effort: bool = False) -> Dict[str, Any]:
+    """Decode a `StorageProposal`; sets and maps come back sorted and deduplicated like their Rust BTree types.
+
+    In `best_effort` mode a failure stops decoding (Borsh has no offsets to resume from) and the fields read so far
+    are returned with an `errors` list naming the field that failed.
+    """
+    if version not in PROPOSAL_LAYOUTS:
+        raise ValueError(f"Unsupported proposal layout version '{version}', supported: {', '.join(PROPOSAL_LAYOUTS)}")
+    reader = Reader(data)
+    errors = "replace" if replace_invalid_utf8 else "strict"
+    steps = (
+        ("id", lambda: {"id": reader.u64()}),
+        ("content", lambda: _read_content(reader, errors)),
+        ("author", lambda: {"author": read_address(reader)}),
+        ("proposal_type", lambda: _read_proposal_type(reader, version)),
+        ("voting_start_epoch", lambda: {"voting_start_epoch": reader.u64()}),
+        ("voting_end_epoch", lambda: {"voting_end_epoch": reader.u64()}),
+        ("grace_epoch", lambda: {"grace_epoch": reader.u64()}),
+        ("trailing_bytes", lambda: reader.finish() or {}),
+    )
+    proposal = {}
+    for field, step in steps:
+        try:
+            proposal.update(step())
+        except ValueError as e:
+            if not best_effort:
+                raise
+            proposal["errors"] = [{"field": field, "error": str(e)}]
+            break
+    return proposal
+
+
+def proposal_content_parse(data: bytes, replace_invalid_utf8: bool = False) -> str:
+    """Decode a proposal's content map, as stored under its own governance key, into a JSON object."""
+    reader = Reader(data)
+    content = _read_content(reader, "replace" if replace_invalid_utf8 else "strict")["content"]
+    reader.finish()
+    return _to_json(content)
+
+
+def _proposal_status(proposal: Dict[str, Any], current_epoch: int, exclusive_end: bool = False) -> Optional[str]:
+    if "voting_start_epoch" not in proposal or "voting_end_epoch" not in proposal:
+        return None
+    if current_epoch < proposal["voting_start_epoch"]:
+        return PROPOSAL_STATUSES[0]
+    if current_epoch < proposal["voting_end_epoch"] or \
+            (current_epoch == proposal["voting_end_epoch"] and not exclusive_end):
+        return PROPOSAL_STATUSES[1]
+    return PROPOSAL_STATUSES[2]
+
+
+def _epoch_boundary(proposal: Dict[str, Any], current_epoch: int) -> Optional[str]:
+    for field in ("voting_start", "voting_end"):
+        if proposal.get(f"{field}_epoch") == current_epoch:
+            return field
+    return None
+
+
+def _epoch_span(proposal: Dict[str, Any], start: str, end: str) -> Optional[int]:
+    if start not in proposal or end not in proposal:
+        return None
+    return proposal[end] - proposal[start]
+
+
+def _data_valid(proposal: Dict[str, Any]) -> Optional[bool]:
+    if "type_index" in proposal:
+        return proposal["type_index"] != 0 or proposal["payload"] is None or len(proposal["payload"]) == HASH_LEN
+    if any(error["field"] == "proposal_type" for error in proposal.get("errors", [])):
+        return False
+    return None
+
+
+def _author_valid(proposal: Dict[str, Any]) -> bool:
+    author = proposal.get("author")
+    return author is not None and author.kind != "Internal"
+
+
+def _is_executable(proposal: Dict[str, Any]) -> Optional[bool]:
+    if "type_index" not in proposal:
+        return None
+    return proposal["type_index"] == 0 and proposal["payload"] is not None
+
+
+def _content_bytes(content: Optional[Dict[str, str]]) -> Optional[int]:
+    if content is None:
+        return None
+    return sum(len(key.encode("utf-8")) + len(value.encode("utf-8")) for key, value in content.items())
+
+
+def _write_content(writer: Writer, content: Dict[str, str]) -> None:
+    writer.u32(len(content))
+    for key, value in sorted(content.items()):
+        writer.string(key)
+        writer.string(value)
+
+
+def _invalid_pgf_targets(proposal: Dict[str, Any]) -> List[Dict[str, str]]:
+    if proposal.get("type_index") != 2:
+        return []
+    invalid = []
+    for _, _, target in proposal["payload"]:
+        if target[0] == 0:
+            if Address.decode(target[1]).kind == "Internal":
+                invalid.append({"target": target[1], "reason": "internal address"})
+            continue
+        try:
+            bech32m.decode(target[1], allow_bech32=True)
+        except ValueError as e:
+            invalid.append({"target": target[1], "reason": str(e)})
+    return invalid
+
+
+def _nested_json(value: str) -> Any:
+    """Return the JSON object or array a content value encodes, unwrapping string-encoded layers, else None."""
+    decoded = value
+    for _ in range(NESTED_JSON_DEPTH):
+        try:
+            decoded = json.loads(decoded)
+        except ValueError:
+            return None
+        if not isinstance(decoded, str):
+            break
+    return decoded if isinstance(decoded, (dict, list)) else None
+
+
+def _content_hash(content: Dict[str, str]) -> str:
+    writer = Writer()
+    _write_content(writer, content)
+    return _sha256_hex(writer.data)
+
+
+def _normalize_content_keys(content: Dict[str, str]) -> Dict[str, str]:
+    normalized = {}
+    for key, value in content.items():
+        lowered = key.lower()
+        if lowered in normalized:
+            raise ValueError(f"Content keys collide after normalization: '{lowered}'")
+        normalized[lowered] = value
+    return dict(sorted(normalized.items()))
+
+
+def proposal_parse(data: bytes, current_epoch: int, normalize_keys: bool = False,
+                   version: str = CURRENT_PROPOSAL_LAYOUT, replace_invalid_utf8: bool = False,
+                   best_effort: bool = False, fields: Optional[List[str]] = None,
+                   exclusive_end: bool = False, empty_data: Optional[str] = None, content_hash: bool = False,
+                   check_targets: bool = False, include_input_hash: bool = False, content_as_pairs: bool = False,
+                   expected_id: Optional[int] = None, decode_nested_json: bool = False, sort_key: bool = False,
+                   verify_author: bool = False) -> str:
+    """Decode a stored proposal, extending the output of the compiled `rust_py.proposal_parse`.
+
+    The output fields are described with `PROPOSAL_FIELDS`. Options:
+    - `normalize_keys`: lowercase and sort content keys, raising when two keys only differ by case.
+    - `version`: one of `PROPOSAL_LAYOUTS`, to read blobs written before an upgrade.
+    - `replace_invalid_utf8`: substitute U+FFFD for invalid UTF-8 in the content instead of raising.
+    - `best_effort`: return the fields decoded before a failure plus an `errors` array, see `PROPOSAL_FIELD_SOURCES`.
+    - `fields`: only compute and emit these `PROPOSAL_FIELDS`; `errors` is always kept.
+    - `exclusive_end`: report `voting_end_epoch` itself as `ended` instead of `on-going`.
+    - `empty_data`: how to emit an empty `data`, one of `EMPTY_DATA_MODES`; defaults to the `ParseConfig` one.
+    - `expected_id`: raise if the decoded `id` differs, i.e. the blob was fetched under the wrong key.
+    - `content_as_pairs`: emit `content` as `[{key, value}]` in map order.
+    - `decode_nested_json`: decode content values holding a JSON object or array, up to `NESTED_JSON_DEPTH` string
+      layers, and list their keys in `nested_json_keys`, null when `fields` leaves out `content`.
+
+    The remaining options each add a field after `fields` filtering:
+    - `content_hash`: SHA-256 of the content as stored, after `normalize_keys`; null if it could not be decoded.
+    - `include_input_hash`: `input_hash`, the SHA-256 of `data` as given.
+    - `check_targets`: `invalid_targets`, the unpayable PGF targets (internal addresses, malformed IBC receivers).
+    - `sort_key`: `voting_start_epoch` and `id`, zero-padded to 20 digits and joined by `:`.
+    - `verify_author`: `author_verified`, always false: `StorageProposal` stores no signature, only the author's
+      `InitProposal` transaction does.
+    """
+    if empty_data is None:
+        empty_data = get_config().empty_data
+    if empty_data not in EMPTY_DATA_MODES:
+        raise ValueError(f"Unknown empty_data mode '{empty_data}', supported: {', '.join(EMPTY_DATA_MODES)}")
+    if fields is not None:
+        unknown = [field for field in fields if field not in PROPOSAL_FIELDS]
+        if unknown:
+            raise ValueError(f"Unknown proposal fields: {', '.join(unknown)}; supported: {', '.join(PROPOSAL_FIELDS)}")
+    proposal = _decode_proposal(data, version, replace_invalid_utf8, best_effort)
+    if expected_id is not None and proposal.get("id", expected_id) != expected_id:
+        raise ValueError(f"Decoded proposal id {proposal['id']} does not match the expected id {expected_id}")
+    if normalize_keys and "content" in proposal:
+        proposal["content"] = _normalize_content_keys(proposal["content"])
+    output = _proposal_fields(proposal, current_epoch, exclusive_end, fields)
+    nested_keys = [] if "content" in output else None
+    if decode_nested_json and output.get("content") is not None:
+        content = dict(output["content"])
+        for key, value in content.items():
+            nested = _nested_json(value)
+            if nested is not None:
+                content[key] = nested
+                nested_keys.append(key)
+        output["content"] = content
+    if content_as_pairs and output.get("content") is not None:
+        output["content"] = [{"key": key, "value": value} for key, value in output["content"].items()]
+    if output.get("data") == "" and empty_data != "string":
+        output["data"] = None
+        if empty_data == "omit":
+            del output["data"]
+    if content_hash:
+        output["content_hash"] = _content_hash(proposal["content"]) if "content" in proposal else None
+    if check_targets:
+        output["invalid_targets"] = _invalid_pgf_targets(proposal)
+    if include_input_hash:
+        output["input_hash"] = _sha256_hex(data)
+    if decode_nested_json:
+        output["nested_json_keys"] = nested_keys
+    if sort_key:
+        output["sort_key"] = (f"{proposal['voting_start_epoch']:020}:{proposal['id']:020}"
+                              if "voting_start_epoch" in proposal else None)
+    if best_effort:
+        output = {key: value for key, value in output.items()
+                  if value is not None or all(source in proposal for source in PROPOSAL_FIELD_SOURCES.get(key, ()))}
+        output["errors"] = proposal.get("errors", [])
+    if verify_author:
+        output["author_verified"] = False
+    return _to_json(output)
+
+
+def _proposal_fields(proposal: Dict[str, Any], current_epoch: int, exclusive_end: bool = False,
+                     fields: Optional[List[str]] = None) -> Dict[str, Any]:
+    """Build the `proposal_parse` output fields, in `PROPOSAL_FIELDS` order; undecoded ones are None.
+
+    Only the `fields` given, or all of them, are computed.
+    """
+    builders = {
+        "id": lambda: proposal.get("id"),
+        "proposal_type": lambda: proposal.get("proposal_type"),
+        "type_index": lambda: proposal.get("type_index"),
+        "author": lambda: _address_string(proposal["author"]) if _author_valid(proposal) else None,
+        "author_valid": lambda: _author_valid(proposal),
+        "content": lambda: proposal.get("content"),
+        "content_bytes": lambda: _content_bytes(proposal.get("content")),
+        "content_field_count": lambda: len(proposal["content"]) if "content" in proposal else None,
+        "voting_start_epoch": lambda: proposal.get("voting_start_epoch"),
+        "voting_end_epoch": lambda: proposal.get("voting_end_epoch"),
+        "grace_epoch": lambda: proposal.get("grace_epoch"),
+        "voting_duration_epochs": lambda: _epoch_span(proposal, "voting_start_epoch", "voting_end_epoch"),
+        "grace_duration_epochs": lambda: _epoch_span(proposal, "voting_end_epoch", "grace_epoch"),
+        "status": lambda: _proposal_status(proposal, current_epoch, exclusive_end),
+        "epoch_boundary": lambda: _epoch_boundary(proposal, current_epoch),
+        "data": lambda: proposal.get("data"),
+        "data_valid": lambda: _data_valid(proposal),
+        "is_executable": lambda: _is_executable(proposal),
+        "has_code": lambda: _is_executable(proposal),
+        "voting_power_epoch": lambda: proposal.get("voting_end_epoch"),
+    }
+    return {name: build() for name, build in builders.items() if fields is None or name in fields}
+
+
+class ProposalContent(Mapping):
+    """Read-only view of a proposal's content map, iterating keys in the on-chain (sorted) order."""
+
+    def __init__(self, content: Dict[str, str]):
+        self._content = dict(content)
+
+    def __getitem__(self, key: str) -> str:
+        return self._content[key]
+
+    def __iter__(self) -> Iterator[str]:
+        return iter(self._content)
+
+    def __len__(self) -> int:
+        return len(self._content)
+
+    def __repr__(self) -> str:
+        return f"ProposalContent({self._content!r})"
+
+
+class Proposal:
+    """Decoded proposal exposing the `proposal_parse` fields as attributes, with `content` as a `ProposalContent`."""
+
+    def __init__(self, fields: Dict[str, Any]):
+        for name in PROPOSAL_FIELDS:
+            setattr(self, name, fields[name])
+        self.content = ProposalContent(fields["content"])
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {name: dict(self.content) if name == "content" else getattr(self, name) for name in PROPOSAL_FIELDS}
+
+    def __repr__(self) -> str:
+        return f"Proposal(id={self.id}, proposal_type={self.proposal_type!r}, status={self.status!r})"
+
+
+def proposal_parse_obj(data: bytes, current_epoch: int, normalize_keys: bool = False,
+                       version: str = CURRENT_PROPOSAL_LAYOUT, replace_invalid_utf8: bool = False,
+                       exclusive_end: bool = False) -> Proposal:
+    """Decode a stored proposal into a `Proposal` object instead of a JSON string.
+
+    The options are the `proposal_parse` ones of the same name; its output-shaping options have no object equivalent.
+    """
+    proposal = _decode_proposal(data, version, replace_invalid_utf8)
+    if normalize_keys:
+        proposal["content"] = _normalize_content_keys(proposal["content"])
+    return Proposal(_proposal_fields(proposal, current_epoch, exclusive_end))
+
+
+def _governance_violations(params: Dict[str, Any], content_size: int, start: int, end: int,
+                           grace: int) -> List[str]:
+    checks = (
+        ("max_proposal_content_size", lambda limit: content_size <= limit, f"content is {content_size} bytes"),
+        ("min_proposal_voting_period", lambda limit: end - start >= limit, f"voting period is {end - start} epochs"),
+        # The governance VP bounds the whole proposal, voting start through grace, which also bounds the voting period
+        ("max_proposal_period", lambda limit: grace - start <= limit,
+         f"proposal period (voting start to grace) is {grace - start} epochs"),
+        ("min_proposal_grace_epochs", lambda limit: grace - end >= limit, f"grace period is {grace - end} epochs"),
+    )
+    violations = []
+    for name, check, actual in checks:
+        if name in params and not check(int(params[name])):
+            violations.append(f"{name} is {params[name]} but {actual}")
+    return violations
+
+
+def build_proposal(proposal_id: int, author: str, type_tag: str, content: Dict[str, str], start: int, end: int,
+                   grace: int, current_epoch: int = 0, params_json: Optional[str] = None) -> Tuple[str, bytes]:
+    """Assemble a `StorageProposal` from its fields and return its `proposal_parse` JSON and Borsh encoding.
+
+    `type_tag` is one of `PROPOSAL_TYPES`; PGF proposals are built with an empty set of actions and default ones
+    without wasm code. The author must be an established or implicit account, content keys and values must be
+    strings and the epochs must satisfy `start < end < grace`. The JSON is produced by decoding the bytes back, with
+    the status taken at `current_epoch`.
+
+    `params_json` is the `GovernanceParameters` JSON; when given, the encoded content size, the voting period and the
+    grace period are checked against it and every violated limit is reported in one error. Other keys are ignored,
+    including `min_proposal_fund`, which applies to the funds locked by the submitting transaction.
+    """
+    if type_tag not in PROPOSAL_TYPES:
+        raise ValueError(f"Unknown proposal type '{type_tag}', supported: {', '.join(PROPOSAL_TYPES)}")
+    for name, value in (("id", proposal_id), ("start", start), ("end", end), ("grace", grace)):
+        _check_u64(name, value)
+    if not start < end < grace:
+        raise ValueError(f"Epochs must satisfy start < end < grace, got {start}, {end}, {grace}")
+    author_address = Address.decode(author)
+    if author_address.kind == "Internal":
+        raise ValueError(f"Proposal author must be an established or implicit account, got {author}")
+    if not all(isinstance(key, str) and isinstance(value, str) for key, value in content.items()):
+        raise ValueError("Proposal content keys and values must be strings")
+    writer = Writer()
+    writer.u64(proposal_id)
+    _write_content(writer, content)
+    content_size = len(writer.data) - 8
+    if params_json is not None:
+        violations = _governance_violations(json.loads(params_json), content_size, start, end, grace)
+        if violations:
+            raise ValueError(f"Proposal violates governance parameters: {'; '.join(violations)}")
+    write_address(writer, author_address)
+    writer.u8(PROPOSAL_TYPES.index(type_tag))
+    if type_tag == "Default":
+        writer.u8(0)
+    else:
+        writer.u32(0)
+    for epoch in (start, end, grace):
+        writer.u64(epoch)
+    data = bytes(writer.data)
+    return proposal_parse(data, current_epoch), data
+
+
+def _to_datetime(value: Union[int, float, str]) -> datetime:
+    if isinstance(value, (int, float)):
+        return datetime.fromtimestamp(value, tz=timezone.utc)
+    parsed = datetime.fromisoformat(value.replace("Z", "+00:00"))
+    return parsed if parsed.tzinfo else parsed.replace(tzinfo=timezone.utc)
+
+
+def _epoch_time(epoch: int, known: List[Tuple[int, datetime]]) -> Tuple[Optional[datetime], bool]:
+    for i, (known_epoch, time) in enumerate(known):
+        if known_epoch == epoch:
+            return time, False
+        if known_epoch > epoch:
+            if i == 0:
+                break
+            before_epoch, before_time = known[i - 1]
+            fraction = (epoch - before_epoch) / (known_epoch - before_epoch)
+            return before_time + (time - before_time) * fraction, True
+    return None, False
+
+
+def proposal_timeline(data: bytes, epoch_times: Dict[int, Union[int, float, str]]) -> str:
+    """Place a stored proposal's voting start, voting end and grace epochs on a timeline.
+
+    `epoch_times` maps epochs to their start time (unix seconds or ISO 8601). An epoch missing from it is linearly
+    interpolated between the nearest known epochs on either side and listed under `interpolated`; outside the known
+    range its time is null, as epoch lengths drift too much to extrapolate.
+    """
+    proposal = _decode_proposal(data)
+    known = sorted((epoch, _to_datetime(time)) for epoch, time in epoch_times.items())
+    timeline = {"id": proposal["id"]}
+    interpolated_fields = []
+    for field in ("voting_start", "voting_end", "grace"):
+        epoch = proposal[f"{field}_epoch"]
+        time, interpolated = _epoch_time(epoch, known)
+        timeline[f"{field}_epoch"] = epoch
+        timeline[f"{field}_time"] = time.isoformat() if time is not None else None
+        if interpolated:
+            interpolated_fields.append(f"{field}_time")
+    timeline["interpolated"] = interpolated_fields
+    return _to_json(timeline)
+
+
+def _summary_entry(blob: bytes, current_epoch: int) -> Dict[str, Any]:
+    proposal = _decode_proposal(blob)
+    return {
+        "id": proposal["id"],
+        "proposal_type": proposal["proposal_type"],
+        "status": _proposal_status(proposal, current_epoch),
+        "is_executable": _is_executable(proposal),
+        "voting_start_epoch": proposal["voting_start_epoch"],
+        "voting_end_epoch": proposal["voting_end_epoch"],
+    }
+
+
+def proposal_summary(blobs: List[bytes], current_epoch: int, epoch_duration_secs: Optional[int] = None,
+                     current_epoch_start_time: Optional[Union[int, float, str]] = None,
+                     workers: Optional[int] = None, start_histogram: bool = False) -> str:
+    """Summarize many stored proposals: counts by status and type plus a compact entry per proposal.
+
+    With `start_histogram` the summary also has `by_start_epoch`, the number of proposals per voting start epoch in
+    ascending order (keys are epoch strings, as JSON requires).
+
+    Given both `epoch_duration_secs` and `current_epoch_start_time` (unix seconds or ISO 8601), every entry also
+    gets an `estimated_voting_end` timestamp for when the epoch after `voting_end_epoch` begins, since voting stays
+    open through `voting_end_epoch` as `status` reports. Epoch lengths drift in practice, so these are approximations
+    and the summary is marked with `"time_estimates": "approximate"`.
+
+    `workers` > 1 decodes the blobs in that many processes; entries keep the input order either way.
+    """
+    estimate = epoch_duration_secs is not None and current_epoch_start_time is not None
+    epoch_start = _to_datetime(current_epoch_start_time) if estimate else None
+    decode = functools.partial(_summary_entry, current_epoch=current_epoch)
+    entries = _map_in_processes(decode, blobs, workers)
+    by_status = {status: 0 for status in PROPOSAL_STATUSES}
+    by_type = {proposal_type: 0 for proposal_type in PROPOSAL_TYPES}
+    for entry in entries:
+        by_status[entry["status"]] += 1
+        by_type[entry["proposal_type"]] += 1
+        if estimate:
+            offset = (entry["voting_end_epoch"] + 1 - current_epoch) * epoch_duration_secs
+            entry["estimated_voting_end"] = (epoch_start + timedelta(seconds=offset)).isoformat()
+    summary = {"total": len(entries), "by_status": by_status, "by_type": by_type, "proposals": entries}
+    if start_histogram:
+        by_start_epoch = {}
+        for entry in entries:
+            by_start_epoch[entry["voting_start_epoch"]] = by_start_epoch.get(entry["voting_start_epoch"], 0) + 1
+        summary["by_start_epoch"] = {str(epoch): count for epoch, count in sorted(by_start_epoch.items())}
+    if estimate:
+        summary["time_estimates"] = "approximate"
+    return _to_json(summary)
+
+
+async def proposal_summary_async(blobs: List[bytes], current_epoch: int, **options) -> str:
+    """Run `proposal_summary` in the default executor so an event loop stays responsive while it decodes."""
+    loop = asyncio.get_running_loop()
+    return await loop.run_in_executor(None, functools.partial(proposal_summary, blobs, current_epoch, **options))
+
+
+def proposals_parse_ndjson(blobs: List[bytes], current_epoch: int, **options) -> str:
+    """Decode many stored proposals into newline-delimited JSON, one `proposal_parse` object per line.
+
+    `options` are passed to `proposal_parse`. A blob that fails to decode becomes an `{"index": i, "error": ...}` line
+    in its place instead of aborting the batch; every line, including the last, ends with a newline.
+    """
+    lines = []
+    for index, blob in enumerate(blobs):
+        try:
+            lines.append(proposal_parse(blob, current_epoch, **options))
+        except ValueError as e:
+            lines.append(_to_json({"index": index, "error": str(e)}))
+    return "".join(f"{line}\n" for line in lines)
+
+
+def _read_vote(reader: Reader) -> Dict[str, str]:
+    return {
+        "validator": read_address(reader).encode(),
+        "delegator": read_address(reader).encode(),
+        "data": VOTE_CHOICES[reader.tag(len(VOTE_CHOICES))],
+    }
+
+
+def _decode_votes(data: bytes) -> List[Dict[str, str]]:
+    reader = Reader(data)
+    votes = reader.vec(lambda: _read_vote(reader))
+    reader.finish()
+    return votes
+
+
+def _vote_conflicts(votes: List[Dict[str, str]]) -> List[Dict[str, Any]]:
+    by_delegator = {}
+    for vote in votes:
+        by_delegator.setdefault(vote["delegator"], []).append(vote)
+    return [{"delegator": delegator, "votes": [{"validator": v["validator"], "data": v["data"]} for v in cast]}
+            for delegator, cast in by_delegator.items() if len({v["data"] for v in cast}) > 1]
+
+
+def _vote_power(vote: Dict[str, str], stake: Dict[str, Union[int, str]],
+                delegations: Optional[Dict[str, Dict[str, Union[int, str]]]]) -> Optional[str]:
+    if vote["delegator"] == vote["validator"]:
+        power = stake.get(vote["validator"])
+    else:
+        power = (delegations or {}).get(vote["delegator"], {}).get(vote["validator"])
+    return str(int(power)) if power is not None else None
+
+
+def votes_parse(data: bytes, detect_conflicts: bool = False, only: Optional[str] = None,
+                stake: Optional[Dict[str, Union[int, str]]] = None,
+                delegations: Optional[Dict[str, Dict[str, Union[int, str]]]] = None,
+                sort: Optional[str] = None) -> str:
+    """Decode the votes cast on a proposal.
+
+    With `detect_conflicts` the output becomes `{votes, conflicts}`, where `conflicts` lists the delegators whose
+    votes disagree with each other, usually because they were cast through different validators. `only` keeps the
+    votes of one choice from `VOTE_CHOICES`; conflicts are still detected across all votes.
+
+    Given a validator -> stake map in `stake`, every vote gets a `power` integer string: a validator's own vote (where
+    delegator and validator are the same) carries its stake, and a delegator's vote the amount it bonded to that
+    validator, looked up in the delegator -> {validator: amount} map `delegations`. Unknown entries get a null power.
+
+    `sort` orders the votes by one of `VOTE_SORT_KEYS` instead of the decoded order: `validator` then delegator,
+    `delegator` then validator (addresses in their canonical order), or `power` descending with null powers last and
+    ties broken by validator and delegator, which needs `stake`.
+    """
+    if only is not None and only not in VOTE_CHOICES:
+        raise ValueError(f"Unknown vote choice '{only}', supported: {', '.join(VOTE_CHOICES)}")
+    if sort is not None and sort not in VOTE_SORT_KEYS:
+        raise ValueError(f"Unknown vote sort key '{sort}', supported: {', '.join(VOTE_SORT_KEYS)}")
+    if sort == "power" and stake is None:
+        raise ValueError("Sorting votes by power needs stake")
+    votes = _decode_votes(data)
+    selected = [vote for vote in votes if vote["data"] == only] if only is not None else votes
+    if stake is not None:
+        for vote in selected:
+            vote["power"] = _vote_power(vote, stake, delegations)
+    if sort == "validator":
+        selected = sorted(selected, key=lambda v: (v["validator"], v["delegator"]))
+    elif sort == "delegator":
+        selected = sorted(selected, key=lambda v: (v["delegator"], v["validator"]))
+    elif sort == "power":
+        selected = sorted(selected, key=lambda v: (v["power"] is None, -int(v["power"] or 0), v["validator"],
+                                                   v["delegator"]))
+    if detect_conflicts:
+        return _to_json({"votes": selected, "conflicts": _vote_conflicts(votes)}, sort_keys=True)
+    return _to_json(selected, sort_keys=True)
+
+
+def votes_count(data: bytes, verify: bool = False) -> int:
+    """Count the votes in a Borsh `Vec<Vote>` from its length prefix alone.
+
+    The prefix is only checked against the shortest possible encoding (two internal addresses and a choice, 5 bytes
+    per vote); `verify` decodes every vote instead, raising on malformed input like `votes_parse`.
+    """
+    if verify:
+        return len(_decode_votes(data))
+    reader = Reader(data)
+    count = reader.u32()
+    if len(data) - reader.pos < count * 5:
+        raise ValueError("Decoding failed: Unexpected length of input")
+    return count
+
+
+def votes_digest(data: bytes) -> str:
+    """Return the uppercase hex SHA-256 of a raw votes blob, as `proposal_parse` computes `input_hash`.
+
+    The blob is not decoded, so a cached `votes_parse` result can be reused while the digest is unchanged; Borsh
+    encodes a value one way only and the node returns votes in storage key order, so an unchanged vote set always
+    gives the same digest.
+    """
+    return _sha256_hex(data)
+
+
+def votes_parse_csv(data: bytes) -> str:
+    """Decode the votes cast on a proposal into CSV with a `validator,delegator,data` header row."""
+    return _to_csv(_decode_votes(data), ("validator", "delegator", "data"))
+
+
+def votes_parse_columns(data: bytes) -> Dict[str, List[str]]:
+    """Decode the votes cast on a proposal into `{validators, delegators, choices}`, equal-length lists in vote order.
+
+    Returned as Python lists rather than JSON, so `pandas.DataFrame(votes_parse_columns(data))` needs no per-row
+    parsing.
+    """
+    votes = _decode_votes(data)
+    return {"validators": [vote["validator"] for vote in votes], "delegators": [vote["delegator"] for vote in votes],
+            "choices": [vote["data"] for vote in votes]}
+
+
+def result_breakdown_parse(data: bytes, stake: Dict[str, Union[int, str]],
+                           delegations: Optional[Dict[str, Dict[str, Union[int, str]]]] = None) -> str:
+    """Break a tally down per validator into `[{validator, yay_power, nay_power, abstain_power}]`.
+
+    `ProposalResult` only stores totals; the per-validator split comes from the proposal's votes
+    (`/vp/governance/proposal/{id}/votes`, passed as `data`) and the stake at the tally epoch, with `stake` and
+    `delegations` shaped as in `votes_parse`. Following the SDK's tally, a validator's vote counts its whole stake,
+    and a delegator voting through it moves its bonded amount from the validator's choice to its own. Votes whose
+    power is unknown are left out; when that is the validator's own vote, delegator votes through it are only added.
+    """
+    votes = _decode_votes(data)
+    breakdown = {}
+    own_choice = {vote["validator"]: vote["data"] for vote in votes
+                  if vote["delegator"] == vote["validator"] and _vote_power(vote, stake, delegations) is not None}
+    for vote in votes:
+        power = _vote_power(vote, stake, delegations)
+        if power is None:
+            continue
+        powers = breakdown.setdefault(vote["validator"], dict.fromkeys(VOTE_CHOICES, 0))
+        powers[vote["data"]] += int(power)
+        if vote["delegator"] != vote["validator"] and vote["validator"] in own_choice:
+            powers[own_choice[vote["validator"]]] -= int(power)
+    return _to_json([{"validator": validator, "yay_power": str(powers["Yay"]), "nay_power": str(powers["Nay"]),
+                      "abstain_power": str(powers["Abstain"])} for validator, powers in sorted(breakdown.items())])
+
+
+def _genesis_key(entry: Any) -> Optional[str]:
+    return entry.get("pk") if isinstance(entry, dict) else entry
+
+
+def genesis_validator_parse(transactions: Dict[str, Any]) -> str:
+    """Extract validator entries from a loaded genesis `transactions.toml` mapping.
+
+    Genesis validators are TOML `validator_account` records rather than Borsh PoS types, so this takes the mapping
+    produced by a TOML loader. `initial_stake` sums the `bond` records targeting each validator.
+    """
+    stakes = {}
+    for bond in transactions.get("bond", []):
+        try:
+            amount = Decimal(str(bond["amount"]))
+        except (KeyError, InvalidOperation):
+            raise ValueError(f"Invalid genesis bond: {bond}")
+        stakes[bond.get("validator")] = stakes.get(bond.get("validator"), Decimal(0)) + amount
+    validators = []
+    for account in transactions.get("validator_account", []):
+        if "address" not in account:
+            raise ValueError("Genesis validator account is missing an address")
+        stake = stakes.get(account["address"], Decimal(0))
+        validators.append({
+            "address": account["address"],
+            "consensus_key": _genesis_key(account.get("consensus_key")),
+            "commission_rate": account.get("commission_rate"),
+            "max_commission_rate_change": account.get("max_commission_rate_change"),
+            "initial_stake": format(stake.normalize(), "f") if stake else "0",
+        })
+    return _to_json(validators)
+
+
+class VotesDecoder:
+    """Incrementally decode a Borsh `Vec<Vote>` fed in arbitrary chunks.
+
+    Borsh records carry no framing, so a vote is only decoded once all of its bytes have arrived; a partial trailing
+    record stays buffered until the next `feed`. A malformed vote leaves no way to find the next record, so after
+    `feed` raises every later `feed` raises the same error; the votes decoded before it can still be drained.
+    """
+
+    def __init__(self):
+        self._buffer = bytearray()
+        self._remaining = None
+        self._votes = []
+        self._error = None
+
+    @property
+    def finished(self) -> bool:
+        return self._remaining == 0
+
+    def feed(self, data: bytes) -> None:
+        if self._error is not None:
+            raise self._error
+        self._buffer += data
+        reader = Reader(self._buffer)
+        start = 0
+        try:
+            if self._remaining is None:
+                self._remaining = reader.u32()
+            while self._remaining:
+                start = reader.pos
+                try:
+                    self._votes.append(_read_vote(reader))
+                except UnexpectedEnd:
+                    reader.pos = start
+                    break
+                self._remaining -= 1
+        except UnexpectedEnd:
+            pass
+        except ValueError as e:
+            reader.pos = start
+            self._error = e
+            raise
+        finally:
+            del self._buffer[:reader.pos]
+        if self.finished and self._buffer:
+            raise ValueError("Decoding failed: Not all bytes read")
+
+    def drain(self) -> List[Dict[str, str]]:
+        votes, self._votes = self._votes, []
+        return votes
+
+
+# Parsers reachable through the generic dispatchers: kind -> (parser, output is JSON, takes the current epoch)
+PARSERS = {
+    "address": (address_parse, False, False),
+    "payment_address": (payment_address_parse, False, False),
+    "viewing_key": (viewing_key_parse, False, False),
+    "validator_addresses": (validator_addresses_parse, True, False),
+    "hash": (hash_parse, False, False),
+    "change": (change_parse, False, False),
+    "token_amount": (token_amount_parse, False, False),
+    "dec": (dec_parse, False, False),
+    "address_amount_pair": (address_amount_pair_parse, True, False),
+    "address_amount_pairs": (address_amount_pairs_parse, True, False),
+    "balances": (balances_parse, True, False),
+    "delegations": (delegations_parse, True, False),
+    "epoch": (epoch_parse, False, False),
+    "gas_limit": (gas_limit_parse, False, False),
+    "gas_cost": (gas_cost_parse, True, False),
+    "weighted_validators": (weighted_validators_parse, True, False),
+    "validator_metadata": (validator_metadata_parse, True, False),
+    "commission_pair": (commission_pair_parse, True, False),
+    "slashes": (slashes_parse, True, False),
+    "proposal": (proposal_parse, True, True),
+    "proposal_content": (proposal_content_parse, True, False),
+    "proposal_result": (proposal_result_parse, True, False),
+    "votes": (votes_parse, True, False),
+}
+
+
+# Storage key patterns -> kind, tried in order by `parse_keyvals`; extend it for other keys
+KEY_PATTERNS = [
+    (re.compile(r"/balance/[^/]+$"), "token_amount"),
+    (re.compile(r"/proposal/\d+/content$"), "proposal_content"),
+    (re.compile(r"/proposal/\d+/author$"), "address"),
+    (re.compile(r"/proposal/\d+/(start_epoch|end_epoch|grace_epoch)$"), "epoch"),
+    (re.compile(r"/proposal/\d+/result$"), "proposal_result"),
+]
+
+
+_timing_enabled = False
+_timings: Dict[str, List[int]] = {}
+_timings_lock = threading.Lock()
+
+
+def timing_enable(enabled: bool = True) -> None:
+    """Turn per-kind timing of decodes on or off; recorded stats are kept until `timing_reset`.
+
+    Only decodes dispatched by kind are timed (`parse_many`, `to_value`, `parse_and_filter`, `parse_keyvals`,
+    `detect_type` probes, ...); when off, the one extra check is made when a parser is looked up, not per call.
+    """
+    global _timing_enabled
+    _timing_enabled = enabled
+
+
+def timing_reset() -> None:
+    """Discard all recorded timing stats."""
+    with _timings_lock:
+        _timings.clear()
+
+
+def timing_report() -> Dict[str, Dict[str, int]]:
+    """Return `{kind: {count, errors, total_ns, mean_ns}}` for the kinds decoded since the last reset.
+
+    `count` includes the `errors` decodes that raised, whose time is part of `total_ns`; `mean_ns` is rounded down.
+    """
+    with _timings_lock:
+        return {kind: {"count": count, "errors": errors, "total_ns": total_ns, "mean_ns": total_ns // count}
+                for kind, (count, errors, total_ns) in _timings.items()}
+
+
+def _timed(kind: str, parser: Callable[[bytes], str]) -> Callable[[bytes], str]:
+    def timed(data: bytes) -> str:
+        failed = True
+        start = time.perf_counter_ns()
+        try:
+            output = parser(data)
+            failed = False
+            return output
+        finally:
+            elapsed = time.perf_counter_ns() - start
+            with _timings_lock:
+                stats = _timings.setdefault(kind, [0, 0, 0])
+                stats[0] += 1
+                stats[1] += failed
+                stats[2] += elapsed
+    return timed
+
+
+def _bind_parser(kind: str, current_epoch: Optional[int] = None) -> Callable[[bytes], str]:
+    if kind not in PARSERS:
+        raise ValueError(f"Unknown kind '{kind}', supported: {', '.join(PARSERS)}")
+    parser, _, needs_epoch = PARSERS[kind]
+    if needs_epoch:
+        if current_epoch is None:
+            raise ValueError(f"Kind '{kind}' requires current_epoch")
+        parser = functools.partial(parser, current_epoch=current_epoch)
+    return _timed(kind, parser) if _timing_enabled else parser
+
+
+def _parse_kind(kind: str, data: bytes, current_epoch: Optional[int] = None) -> Any:
+    with use_config(replace(get_config(), number_mode="number")):
+        output = _bind_parser(kind, current_epoch)(data)
+    return json.loads(output) if PARSERS[kind][1] else output
+
+
+def parse_many(kind: str, blobs: List[bytes], current_epoch: Optional[int] = None,
+               lenient: Optional[bool] = None) -> List[Any]:
+    """Decode a batch of blobs of one kind into Python objects.
+
+    In `lenient` mode a blob that fails to decode becomes `{"error": ...}`.
+    """
+    lenient = _lenient(lenient)
+    if kind not in PARSERS:
+        raise ValueError(f"Unknown kind '{kind}', supported: {', '.join(PARSERS)}")
+    results = []
+    for blob in blobs:
+        try:
+            results.append(_parse_kind(kind, blob, current_epoch))
+        except ValueError as e:
+            if not lenient:
+                raise
+            results.append({"error": str(e)})
+    return results
+
+
+def _parse_spec(spec: Tuple[str, bytes], current_epoch: Optional[int], lenient: bool, config: ParseConfig) -> Any:
+    kind, blob = spec
+    with use_config(config):
+        try:
+            return _parse_kind(kind, blob, current_epoch)
+        except ValueError as e:
+            if not lenient:
+                raise
+            return {"error": str(e)}
+
+
+def parse_many_kinds(specs: List[Tuple[str, bytes]], current_epoch: Optional[int] = None,
+                     lenient: Optional[bool] = None, workers: Optional[int] = None) -> List[Any]:
+    """Decode a mixed batch of `(kind, data)` pairs into Python objects, in order.
+
+    A blob that fails to decode becomes `{"error": ...}` in `lenient` mode, as in `parse_many`, but kinds are checked
+    up front, so an unknown kind, or one needing `current_epoch` without it, always raises. `workers` > 1 decodes the
+    pairs in that many processes, passing them the `ParseConfig` in effect.
+    """
+    lenient = _lenient(lenient)
+    for kind in {kind for kind, _ in specs}:
+        _bind_parser(kind, current_epoch)
+    decode = functools.partial(_parse_spec, current_epoch=current_epoch, lenient=lenient, config=get_config())
+    return _map_in_processes(decode, specs, workers)
+
+
+def _compile_filter(filter_json: str) -> List[Tuple[str, str, Any]]:
+    spec = json.loads(filter_json)
+    if not isinstance(spec, dict):
+        raise ValueError("Filter must be a JSON object mapping fields to conditions")
+    conditions = []
+    for field, condition in spec.items():
+        if not isinstance(condition, dict):
+            condition = {"eq": condition}
+        for op, operand in condition.items():
+            if op not in FILTER_OPERATORS:
+                raise ValueError(f"Unknown filter operator '{op}' for '{field}', "
+                                 f"supported: {', '.join(FILTER_OPERATORS)}")
+            if isinstance(operand, (dict, list)) and op != "in":
+                raise ValueError(f"Operator '{op}' for '{field}' needs a scalar operand")
+            if op == "in" and not isinstance(operand, list):
+                raise ValueError(f"Operator 'in' for '{field}' needs a list operand")
+            if op in ("gt", "gte", "lt", "lte"):
+                try:
+                    operand = Decimal(str(operand))
+                except InvalidOperation:
+                    raise ValueError(f"Operator '{op}' for '{field}' needs a numeric operand, got {operand!r}")
+            conditions.append((field, op, operand))
+    return conditions
+
+
+def _matches(record: Any, conditions: List[Tuple[str, str, Any]]) -> bool:
+    for field, op, operand in conditions:
+        if not isinstance(record, dict) or field not in record:
+            return False
+        value = record[field]
+        if op in ("gt", "gte", "lt", "lte"):
+            if isinstance(value, bool) or not isinstance(value, (int, str)):
+                return False
+            try:
+                value = Decimal(str(value))
+            except InvalidOperation:
+                return False
+        if not FILTER_OPERATORS[op](value, operand):
+            return False
+    return True
+
+
+def parse_and_filter(kind: str, blobs: List[bytes], filter_json: str,
+                     current_epoch: Optional[int] = None) -> List[Any]:
+    """Decode a batch of blobs of one kind and keep only the records matching `filter_json`.
+
+    Records are the decoded objects, or the elements of list-shaped kinds such as `votes`, flattened across blobs.
+    The filter maps field names to a value, for equality, or to an object of `FILTER_OPERATORS`, all of which must
+    hold, e.g. `{"data": "Yay"}` or `{"status": "on-going", "id": {"gte": 10}}`. Range operators compare integers
+    and decimal strings such as amounts exactly; records missing a field never match.
+    """
+    conditions = _compile_filter(filter_json)
+    records = []
+    for decoded in parse_many(kind, blobs, current_epoch):
+        records.extend(decoded if isinstance(decoded, list) else [decoded])
+    return [record for record in records if _matches(record, conditions)]
+
+
+def parse_keyvals(pairs: List[Tuple[bytes, bytes]], current_epoch: Optional[int] = None) -> str:
+    """Decode a storage prefix iteration, a list of `(key, value)` pairs, into `[{key, kind, value}]`.
+
+    Each key is matched against `KEY_PATTERNS` in order and its value decoded as the first matching kind. A value
+    whose key matches no pattern, or that fails to decode as the matched kind, is kept as `raw_hex` (with the
+    `error` in the latter case) rather than failing the whole iteration.
+    """
+    records = []
+    for key, value in pairs:
+        key_str = bytes(key).decode("utf-8", "replace")
+        kind = next((kind for pattern, kind in KEY_PATTERNS if pattern.search(key_str)), None)
+        record = {"key": key_str, "kind": kind}
+        if kind is None:
+            record["raw_hex"] = bytes(value).hex()
+        else:
+            try:
+                record["value"] = _parse_kind(kind, value, current_epoch)
+            except ValueError as e:
+                record["raw_hex"] = bytes(value).hex()
+                record["error"] = str(e)
+        records.append(record)
+    return _to_json(records)
+
+
+def to_value(kind: str, data: bytes, current_epoch: Optional[int] = None) -> Any:
+    """Decode one blob of any `PARSERS` kind straight into Python objects.
+
+    JSON objects become dicts, arrays lists and numbers ints (amounts stay decimal strings to keep their precision);
+    kinds whose output is a plain string, like `address`, return it unchanged.
+    """
+    return _parse_kind(kind, data, current_epoch)
+
+
+def parse_bench(kind: str, data: bytes, iters: int, current_epoch: Optional[int] = None) -> float:
+    """Decode `data` as `kind` `iters` times and return the mean nanoseconds per decode.
+
+    Only the parser is timed, not loading its JSON output. The blob is decoded once up front so a bad blob raises
+    instead of timing the error path. The pure Python parsers cannot release the GIL, so run it on an otherwise idle
+    interpreter for a representative number.
+    """
+    if iters <= 0:
+        raise ValueError(f"iters must be positive, got {iters}")
+    parser = _bind_parser(kind, current_epoch)
+    parser(data)
+    start = time.perf_counter_ns()
+    for _ in range(iters):
+        parser(data)
+    return (time.perf_counter_ns() - start) / iters
+
+
+# `balances`, `gas_cost` and `delegations` read the same bytes as `address_amount_pairs` (and `validator_addresses`),
+# so detection leaves them out
+DETECTABLE_KINDS = tuple(kind for kind in PARSERS if kind not in ("balances", "gas_cost", "delegations"))
+
+
+def detect_type(data: bytes) -> Optional[str]:
+    """Guess the kind of a blob from `DETECTABLE_KINDS`, or None when it cannot be told apart.
+
+    Borsh carries no type tags, so the only signal is structure: a kind is a candidate when its parser consumes the
+    whole blob without error (proposals are decoded at epoch 0, as the epoch only affects the status). The guess is
+    returned only when exactly one kind is a candidate, which means:
+    - empty input is None, as it is only a provisional proposal result by convention;
+    - any 32-byte blob is None, since it is a valid `hash`, `change`, `token_amount` and `dec` alike;
+    - any 8-byte blob is None, since it is a valid `epoch` and `gas_limit` alike;
+    - a zero-length vector (`00000000`) is None, since every vector kind accepts it.
+    Never raises; pass the result to `parse_many` or a parser directly to override it.
+    """
+    if not data:
+        return None
+    candidates = []
+    for kind in DETECTABLE_KINDS:
+        try:
+            _parse_kind(kind, data, 0)
+        except Exception:
+            continue
+        candidates.append(kind)
+    return candidates[0] if len(candidates) == 1 else None
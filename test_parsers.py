@@ -0,0 +1,156 @@
+import json
+import unittest
+from namada_types import bech32m
+from namada_types.core import TALLY_RESULTS, TALLY_TYPES, U64_MAX, VOTE_CHOICES, Address, Writer, write_address
+from namada_types.parsers import ParseConfig, VotesDecoder, build_proposal, proposal_parse, proposal_result_parse, \
+    use_config, votes_parse
+
+# Voting must start before it ends, so only the grace epoch can reach u64::MAX
+EPOCHS = {"voting_start_epoch": U64_MAX - 2, "voting_end_epoch": U64_MAX - 1, "grace_epoch": U64_MAX}
+
+
+class ProposalIntegerTest(unittest.TestCase):
+    def setUp(self):
+        author = Address(1, bytes(range(20))).encode()
+        _, self.blob = build_proposal(U64_MAX, author, "Default", {"title": "max"}, *EPOCHS.values())
+
+    def test_u64_max_is_a_bare_integer(self):
+        output = proposal_parse(self.blob, 0)
+        self.assertIn(f'"id":{U64_MAX},', output)
+        for field, epoch in EPOCHS.items():
+            self.assertIn(f'"{field}":{epoch},', output)
+        parsed = json.loads(output)
+        self.assertEqual(parsed["id"], U64_MAX)
+        for field, epoch in EPOCHS.items():
+            self.assertEqual(parsed[field], epoch)
+
+    def test_string_number_mode_quotes_u64_max(self):
+        with use_config(ParseConfig(number_mode="string")):
+            parsed = json.loads(proposal_parse(self.blob, 0))
+        self.assertEqual(parsed["id"], str(U64_MAX))
+        for field, epoch in EPOCHS.items():
+            self.assertEqual(parsed[field], str(epoch))
+
+    def test_string_number_mode_is_scoped(self):
+        with use_config(ParseConfig(number_mode="string")):
+            self.assertEqual(json.loads(proposal_parse(self.blob, 0))["id"], str(U64_MAX))
+        self.assertEqual(json.loads(proposal_parse(self.blob, 0))["id"], U64_MAX)
+
+
+def votes_blob(choices: list) -> bytes:
+    writer = Writer()
+    writer.u32(len(choices))
+    for i, choice in enumerate(choices):
+        validator, delegator = Address(1, bytes([i]) * 20), Address(0, bytes([i + 100]) * 20)
+        write_address(writer, validator)
+        write_address(writer, delegator)
+        writer.u8(VOTE_CHOICES.index(choice) if choice in VOTE_CHOICES else choice)
+    return bytes(writer.data)
+
+
+# The u32 length prefix, then two 21-byte addresses and a vote tag per vote
+VOTE_LEN = 43
+
+
+class VotesDecoderTest(unittest.TestCase):
+    def setUp(self):
+        self.blob = votes_blob(["Yay", "Nay", "Abstain"])
+        self.expected = json.loads(votes_parse(self.blob))
+
+    def test_chunked_feeding(self):
+        for size in (1, 7):
+            decoder = VotesDecoder()
+            votes = []
+            for i in range(0, len(self.blob), size):
+                decoder.feed(self.blob[i:i + size])
+                votes += decoder.drain()
+            self.assertTrue(decoder.finished)
+            self.assertEqual(votes, self.expected)
+
+    def test_partial_prefix(self):
+        decoder = VotesDecoder()
+        decoder.feed(self.blob[:2])
+        self.assertFalse(decoder.finished)
+        self.assertEqual(decoder.drain(), [])
+        decoder.feed(self.blob[2:])
+        self.assertEqual(decoder.drain(), self.expected)
+
+    def test_partial_vote(self):
+        decoder = VotesDecoder()
+        decoder.feed(self.blob[:4 + VOTE_LEN + 10])
+        self.assertEqual(decoder.drain(), self.expected[:1])
+        decoder.feed(self.blob[4 + VOTE_LEN + 10:])
+        self.assertTrue(decoder.finished)
+        self.assertEqual(decoder.drain(), self.expected[1:])
+
+    def test_trailing_bytes_after_finished(self):
+        decoder = VotesDecoder()
+        with self.assertRaisesRegex(ValueError, "Not all bytes read"):
+            decoder.feed(self.blob + b"\x00")
+        self.assertEqual(decoder.drain(), self.expected)
+
+    def test_malformed_tag_poisons_decoder(self):
+        blob = votes_blob(["Yay", 7, "Nay"])
+        decoder = VotesDecoder()
+        with self.assertRaises(ValueError) as first:
+            decoder.feed(blob[:4 + 2 * VOTE_LEN])
+        with self.assertRaises(ValueError) as second:
+            decoder.feed(blob[4 + 2 * VOTE_LEN:])
+        self.assertIs(second.exception, first.exception)
+        self.assertFalse(decoder.finished)
+        self.assertEqual([vote["data"] for vote in decoder.drain()], ["Yay"])
+
+
+class Bech32mTest(unittest.TestCase):
+    def test_round_trip(self):
+        payload = bytes([1]) + bytes(range(20))
+        encoded = bech32m.encode("tnam", payload)
+        self.assertEqual(bech32m.decode(encoded), ("tnam", payload))
+        self.assertEqual(bech32m.decode(encoded.upper()), ("tnam", payload))
+        self.assertEqual(Address.decode(Address(1, bytes(range(20))).encode()), Address(1, bytes(range(20))))
+
+    def test_bip350_vector(self):
+        self.assertEqual(bech32m.encode("a", b""), "a1lqfn3a")
+
+    def test_bad_checksum_is_rejected(self):
+        encoded = bech32m.encode("tnam", bytes(21))
+        corrupted = encoded[:-1] + ("q" if encoded[-1] != "q" else "p")
+        with self.assertRaisesRegex(ValueError, "Invalid bech32m checksum"):
+            bech32m.decode(corrupted)
+
+    def test_bech32_variant_needs_opt_in(self):
+        with self.assertRaisesRegex(ValueError, "bech32 variant"):
+            bech32m.decode("a12uel5l")
+        self.assertEqual(bech32m.decode("a12uel5l", allow_bech32=True), ("a", b""))
+
+
+def result_blob(result: str, tally_type: str, total: int, yay: int, nay: int, abstain: int) -> bytes:
+    powers = b"".join(power.to_bytes(32, "little") for power in (total, yay, nay, abstain))
+    return bytes([TALLY_RESULTS.index(result), TALLY_TYPES.index(tally_type)]) + powers
+
+
+class ThresholdPowerTest(unittest.TestCase):
+    def threshold(self, tally_type: str, total: int, yay: int, nay: int, abstain: int) -> str:
+        output = json.loads(proposal_result_parse(result_blob("Rejected", tally_type, total, yay, nay, abstain)))
+        return output["threshold_power"]
+
+    def test_two_thirds(self):
+        # Majority: yay >= 2 * nay; turnout: 3 * (yay + 30) >= 200
+        self.assertEqual(self.threshold("TwoThirds", 100, 0, 10, 20), "37")
+        self.assertEqual(self.threshold("TwoThirds", 100, 0, 30, 0), "60")
+
+    def test_one_half_over_one_third(self):
+        self.assertEqual(self.threshold("OneHalfOverOneThird", 100, 0, 10, 0), "24")
+        self.assertEqual(self.threshold("OneHalfOverOneThird", 100, 0, 30, 10), "31")
+
+    def test_less_one_half_over_one_third_nay(self):
+        # 5 yay passes on its own, but more yay lifts the turnout past a third with a nay majority
+        self.assertEqual(self.threshold("LessOneHalfOverOneThirdNay", 100, 5, 30, 0), "30")
+        self.assertEqual(self.threshold("LessOneHalfOverOneThirdNay", 100, 0, 0, 50), "0")
+
+    def test_zero_total_has_no_threshold(self):
+        self.assertIsNone(self.threshold("TwoThirds", 0, 0, 0, 0))
+
+
+if __name__ == "__main__":
+    unittest.main()